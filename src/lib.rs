@@ -4,12 +4,15 @@ extern crate lazy_static;
 extern crate serde_derive;
 
 pub mod openapi;
+pub mod openapi2postman;
 pub mod postman;
+mod scripts;
 
 pub use anyhow::Result;
 use convert_case::{Case, Casing};
 use indexmap::IndexSet;
 use openapi::v3_0 as openapi3;
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 #[cfg(target_arch = "wasm32")]
 use std::str::FromStr;
@@ -18,15 +21,151 @@ use wasm_bindgen::prelude::*;
 
 static VAR_REPLACE_CREDITS: usize = 20;
 
+// Property names that commonly hold a constant value identifying the shape of an object.
+static DISCRIMINATOR_PROPERTIES: &[&str] = &[
+    "type",
+    "kind",
+    "@type",
+    "_type",
+    "objectType",
+    "object_type",
+    "object",
+    "eventType",
+    "event_type",
+];
+
+static REDACTED: &str = "<redacted>";
+
+// Media types of uploaded files, by file extension.
+static FILE_MEDIA_TYPES: &[(&str, &str)] = &[
+    ("csv", "text/csv"),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "application/javascript"),
+    ("json", "application/json"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("txt", "text/plain"),
+    ("webp", "image/webp"),
+    ("xml", "application/xml"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("zip", "application/zip"),
+];
+
 lazy_static! {
     static ref VARIABLE_RE: regex::Regex = regex::Regex::new(r"\{\{([^{}]*?)\}\}").unwrap();
     static ref URI_TEMPLATE_VARIABLE_RE: regex::Regex =
         regex::Regex::new(r"\{([^{}]*?)\}").unwrap();
+    static ref JAVASCRIPT_RE: regex::Regex = regex::Regex::new(
+        r#"^(function[\s(]|\(function|(var|let|const)\s+[\w$]+\s*=|import\s|export\s|["']use strict["'])"#
+    )
+    .unwrap();
+    static ref YAML_LINE_RE: regex::Regex =
+        regex::Regex::new(r#"^\s*(- |-$|[\w"'.-]+:(\s|$))"#).unwrap();
+    static ref GRAPHQL_OPERATION_RE: regex::Regex =
+        regex::Regex::new(r"\b(?:query|mutation|subscription)\s+([_A-Za-z][_0-9A-Za-z]*)").unwrap();
+    static ref CALLBACK_URL_RE: regex::Regex =
+        regex::Regex::new(r"(?i)^(callback|webhook|hook|notification|notify)[-_]?(url|uri)$")
+            .unwrap();
+    static ref SECRET_KEY_RE: regex::Regex = regex::Regex::new(
+        r"(?i)(authorization|cookie|api[-_]?key|token|password|passwd|secret|session[-_]?id)"
+    )
+    .unwrap();
+    static ref SECRET_VALUE_RES: Vec<regex::Regex> = vec![
+        // JSON Web Tokens
+        regex::Regex::new(r"eyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]*").unwrap(),
+        // Credentials in authorization header values
+        regex::Regex::new(r"(?i)\b(bearer|basic|digest|token)\s+[A-Za-z0-9._~+/=-]{8,}").unwrap(),
+    ];
 }
 
+/// Options for transpiling a collection. New options may be added at any time, so build them from
+/// `TranspileOptions::default()` and the `with_*` methods.
 #[derive(Default)]
+#[non_exhaustive]
 pub struct TranspileOptions {
     pub format: TargetFormat,
+    pub examples: ExampleOptions,
+    pub redaction: RedactionOptions,
+    pub response_description: ResponseDescription,
+    pub spec_version: SpecVersion,
+    /// Annotate operations, tags and examples with the Postman IDs, names, folders and URLs they
+    /// come from, so that the collection can be reconstructed from the output.
+    pub source_annotations: bool,
+    /// Skip the items that fail validation, reporting each one as a warning, instead of failing
+    /// the whole collection.
+    pub lenient: bool,
+}
+
+impl TranspileOptions {
+    pub fn with_format(mut self, format: TargetFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_examples(mut self, examples: ExampleOptions) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    pub fn with_redaction(mut self, redaction: RedactionOptions) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    pub fn with_response_description(mut self, response_description: ResponseDescription) -> Self {
+        self.response_description = response_description;
+        self
+    }
+
+    pub fn with_spec_version(mut self, spec_version: SpecVersion) -> Self {
+        self.spec_version = spec_version;
+        self
+    }
+
+    pub fn with_source_annotations(mut self, source_annotations: bool) -> Self {
+        self.source_annotations = source_annotations;
+        self
+    }
+
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+}
+
+/// Controls which values are replaced with a placeholder before they are copied into examples.
+#[derive(Clone, Debug, Default)]
+pub struct RedactionOptions {
+    /// Redact values of authorization, API key, token, password, secret and cookie fields as
+    /// well as JWT-shaped strings and credentials in authorization header values.
+    pub default_rules: bool,
+    /// Additional field names, matched case-insensitively, whose values are redacted.
+    pub keys: Vec<String>,
+    /// Additional patterns; any matching text in a value is redacted.
+    pub patterns: Vec<regex::Regex>,
+}
+
+/// Controls how much of the collection's example data is embedded in the output.
+#[derive(Clone, Debug, Default)]
+pub struct ExampleOptions {
+    /// Truncate arrays in examples to at most this many items.
+    pub max_array_length: Option<usize>,
+    /// Truncate strings in examples to at most this many characters.
+    pub max_string_length: Option<usize>,
+    /// Drop the examples attached to schema properties when the media type has its own example.
+    pub omit_schema_examples: bool,
+    /// Move media type examples larger than this many bytes of JSON into `components.examples`.
+    pub reference_threshold: Option<usize>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -36,18 +175,40 @@ pub fn from_path(filename: &str, options: TranspileOptions) -> Result<String> {
 }
 
 pub fn from_str(collection: &str, options: TranspileOptions) -> Result<String> {
-    let postman_spec: postman::Spec = serde_json::from_str(collection)?;
-    let oas_spec = Transpiler::transpile(postman_spec);
+    let (oas_definition, _) = from_str_with_warnings(collection, options)?;
+    Ok(oas_definition)
+}
+
+/// Transpiles the collection, also returning the items that were skipped in lenient mode and
+/// the constructs that were lost converting to the requested specification version.
+pub fn from_str_with_warnings(
+    collection: &str,
+    options: TranspileOptions,
+) -> Result<(String, Vec<String>)> {
+    let (postman_spec, mut warnings) =
+        postman::from_str_with_warnings(collection, options.lenient)?;
+    let mut oas_spec = Transpiler::transpile_with_options(postman_spec, &options)?;
+    if options.spec_version == SpecVersion::V2_0 {
+        let conversion = oas_spec.to_v2()?;
+        oas_spec = openapi::OpenApi::V2(conversion.spec);
+        warnings.extend(conversion.warnings);
+    }
     let oas_definition = match options.format {
         TargetFormat::Json => openapi::to_json(&oas_spec),
         TargetFormat::Yaml => openapi::to_yaml(&oas_spec),
     }?;
-    Ok(oas_definition)
+    Ok((oas_definition, warnings))
 }
 
 #[cfg(target_arch = "wasm32")]
 fn from_str_with_format(collection: &str, format: TargetFormat) -> Result<String> {
-    from_str(collection, TranspileOptions { format })
+    from_str(
+        collection,
+        TranspileOptions {
+            format,
+            ..TranspileOptions::default()
+        },
+    )
 }
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
@@ -75,7 +236,7 @@ pub enum TargetFormat {
     Yaml,
 }
 
-impl<'a> Default for TargetFormat {
+impl Default for TargetFormat {
     fn default() -> Self {
         TargetFormat::Yaml
     }
@@ -92,18 +253,108 @@ impl std::str::FromStr for TargetFormat {
     }
 }
 
+/// The version of the OpenAPI specification to emit.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(non_camel_case_types)]
+pub enum SpecVersion {
+    V3_0,
+    /// OpenAPI 3.1, with JSON Schema 2020-12 schemas, top-level webhooks and mutual TLS.
+    V3_1,
+    /// Swagger 2.0, converted from OpenAPI 3.0 with the loss of constructs it cannot express.
+    V2_0,
+}
+
+impl Default for SpecVersion {
+    fn default() -> Self {
+        SpecVersion::V3_0
+    }
+}
+
+impl std::str::FromStr for SpecVersion {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "3.0" => Ok(SpecVersion::V3_0),
+            "3.1" => Ok(SpecVersion::V3_1),
+            "2.0" => Ok(SpecVersion::V2_0),
+            _ => Err("invalid specification version"),
+        }
+    }
+}
+
+/// Where the descriptions of saved responses are taken from. When the preferred source is
+/// missing, the others are tried in turn.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ResponseDescription {
+    /// The name of the saved example.
+    ExampleName,
+    /// The status text of the saved example, e.g. "Not Found".
+    StatusText,
+    /// The standard reason phrase of the status code.
+    ReasonPhrase,
+}
+
+impl Default for ResponseDescription {
+    fn default() -> Self {
+        ResponseDescription::ExampleName
+    }
+}
+
+impl std::str::FromStr for ResponseDescription {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "name" => Ok(ResponseDescription::ExampleName),
+            "status" => Ok(ResponseDescription::StatusText),
+            "reason" => Ok(ResponseDescription::ReasonPhrase),
+            _ => Err("invalid response description"),
+        }
+    }
+}
+
 pub struct Transpiler<'a> {
     variable_map: &'a BTreeMap<String, serde_json::value::Value>,
+    options: &'a TranspileOptions,
 }
 
 struct TranspileState<'a> {
     oas: &'a mut openapi3::Spec,
     operation_ids: &'a mut BTreeMap<String, usize>,
     hierarchy: &'a mut Vec<String>,
+    /// Events of the enclosing folders, outermost first.
+    folder_events: &'a mut Vec<serde_json::Value>,
+    variable_sources: &'a mut BTreeMap<String, VariableSource>,
+    /// Security requirements of the enclosing folders that declare auth, outermost first.
+    folder_security: &'a mut Vec<Vec<openapi3::SecurityRequirement>>,
+    webhooks: &'a mut Vec<Webhook>,
+    /// The operations whose requests present a client certificate, with the certificate's name.
+    certificates: &'a mut Vec<(String, Option<String>)>,
+}
+
+/// A request from a "Webhooks" folder, declared as a callback of the operations that subscribe
+/// to it.
+struct Webhook {
+    name: String,
+    path: String,
+    item: openapi3::PathItem,
+}
+
+/// An operation whose test scripts store a value from its response in a variable.
+struct VariableSource {
+    operation_id: String,
+    /// Runtime expression for the stored value, e.g. `$response.body#/id`.
+    expression: String,
 }
 
 impl<'a> Transpiler<'a> {
-    pub fn transpile(spec: postman::Spec) -> openapi::OpenApi {
+    pub fn transpile(spec: postman::Spec) -> Result<openapi::OpenApi> {
+        Transpiler::transpile_with_options(spec, &TranspileOptions::default())
+    }
+
+    pub fn transpile_with_options(
+        spec: postman::Spec,
+        options: &TranspileOptions,
+    ) -> Result<openapi::OpenApi> {
         let description = extract_description(&spec.info.description);
 
         let mut oas = openapi3::Spec {
@@ -115,14 +366,34 @@ impl<'a> Transpiler<'a> {
                 terms_of_service: None,
                 version: String::from("1.0.0"),
                 title: spec.info.name,
+                ..openapi3::Info::default()
             },
             components: None,
+            security: None,
             external_docs: None,
             paths: BTreeMap::new(),
             servers: Some(Vec::<openapi3::Server>::new()),
             tags: Some(IndexSet::<openapi3::Tag>::new()),
+            extensions: BTreeMap::new(),
         };
 
+        if options.source_annotations {
+            if let Some(id) = &spec.info.postman_id {
+                oas.info.extensions.insert(
+                    "x-postman-id".to_string(),
+                    serde_json::Value::from(id.clone()),
+                );
+            }
+        }
+
+        let events = postman_events(&spec.event, None);
+        if !events.is_empty() {
+            oas.extensions.insert(
+                "x-postman-events".to_string(),
+                serde_json::Value::Array(events),
+            );
+        }
+
         let mut variable_map = BTreeMap::<String, serde_json::value::Value>::new();
         if let Some(var) = spec.variable {
             for v in var {
@@ -138,19 +409,62 @@ impl<'a> Transpiler<'a> {
 
         let mut operation_ids = BTreeMap::<String, usize>::new();
         let mut hierarchy = Vec::<String>::new();
+        let mut folder_events = Vec::<serde_json::Value>::new();
+        let mut variable_sources = BTreeMap::<String, VariableSource>::new();
+        let mut folder_security = Vec::<Vec<openapi3::SecurityRequirement>>::new();
+        let mut webhooks = Vec::<Webhook>::new();
+        let mut certificates = Vec::<(String, Option<String>)>::new();
         let mut state = TranspileState {
             oas: &mut oas,
             operation_ids: &mut operation_ids,
             hierarchy: &mut hierarchy,
+            folder_events: &mut folder_events,
+            variable_sources: &mut variable_sources,
+            folder_security: &mut folder_security,
+            webhooks: &mut webhooks,
+            certificates: &mut certificates,
         };
 
         let transpiler = Transpiler {
             variable_map: &mut variable_map,
+            options,
         };
 
+        if let Some(auth) = &spec.auth {
+            if let Some(security) = transpiler.security_requirements(state.oas, auth) {
+                if !security.is_empty() {
+                    state.oas.security = Some(security);
+                }
+            }
+        }
+
         transpiler.transform(&mut state, &spec.item);
+        // OpenAPI 3.1 declares webhooks at the top level instead.
+        if options.spec_version != SpecVersion::V3_1 {
+            add_callbacks(&mut oas, std::mem::take(&mut webhooks));
+        }
+
+        if let Some(threshold) = options.examples.reference_threshold {
+            reference_large_examples(&mut oas, threshold);
+        }
 
-        openapi::OpenApi::V3_0(Box::new(oas))
+        if options.spec_version != SpecVersion::V3_1 {
+            return Ok(openapi::OpenApi::V3_0(Box::new(oas)));
+        }
+        let mut oas = oas.to_v3_1()?;
+        if !webhooks.is_empty() {
+            // Webhooks are converted by the same means as the paths.
+            let webhooks = openapi3::Spec {
+                paths: webhooks
+                    .into_iter()
+                    .map(|webhook| (webhook.name, webhook.item))
+                    .collect(),
+                ..openapi3::Spec::default()
+            };
+            oas.webhooks = webhooks.to_v3_1()?.paths;
+        }
+        add_mutual_tls(&mut oas, certificates);
+        Ok(openapi::OpenApi::V3_1(Box::new(oas)))
     }
 
     fn transform(&self, state: &mut TranspileState, items: &[postman::Items]) {
@@ -160,11 +474,15 @@ impl<'a> Transpiler<'a> {
                     Some(n) => n,
                     None => "<folder>",
                 };
+                if name.eq_ignore_ascii_case("webhooks") {
+                    self.transform_webhooks(state, i);
+                    continue;
+                }
                 let description = extract_description(&item.description);
 
-                self.transform_folder(state, &i, name, description);
+                self.transform_folder(state, item, i, name, description);
             } else {
-                self.transform_request(state, &item);
+                self.transform_request(state, item);
             }
         }
     }
@@ -172,15 +490,20 @@ impl<'a> Transpiler<'a> {
     fn transform_folder(
         &self,
         state: &mut TranspileState,
+        folder: &postman::Items,
         items: &[postman::Items],
         name: &str,
         description: Option<String>,
     ) {
+        let events = &folder.event;
+        let auth = &folder.auth;
         if let Some(t) = &mut state.oas.tags {
             let mut tag = openapi3::Tag {
                 name: name.to_string(),
                 description,
+                ..openapi3::Tag::default()
             };
+            self.annotate_source(&mut tag.extensions, &folder.id, name);
 
             let mut i: usize = 0;
             while t.contains(&tag) {
@@ -191,10 +514,63 @@ impl<'a> Transpiler<'a> {
             let name = tag.name.clone();
             t.insert(tag);
 
+            let enclosing_events = state.folder_events.len();
+            state
+                .folder_events
+                .extend(postman_events(events, Some(&name)));
+            let security = auth
+                .as_ref()
+                .and_then(|auth| self.security_requirements(state.oas, auth));
+            let has_security = security.is_some();
+            if let Some(security) = security {
+                state.folder_security.push(security);
+            }
             state.hierarchy.push(name);
             self.transform(state, items);
             state.hierarchy.pop();
+            state.folder_events.truncate(enclosing_events);
+            if has_security {
+                state.folder_security.pop();
+            }
+        };
+    }
+
+    /// Transpiles the requests of a "Webhooks" folder into separate paths, which are declared as
+    /// callbacks once the whole collection has been transpiled.
+    fn transform_webhooks(&self, state: &mut TranspileState, items: &[postman::Items]) {
+        let mut oas = openapi3::Spec {
+            tags: Some(IndexSet::<openapi3::Tag>::new()),
+            ..openapi3::Spec::default()
+        };
+        let mut hierarchy = Vec::<String>::new();
+        let mut variable_sources = BTreeMap::<String, VariableSource>::new();
+        let mut folder_security = Vec::<Vec<openapi3::SecurityRequirement>>::new();
+        let mut webhook_state = TranspileState {
+            oas: &mut oas,
+            operation_ids: &mut *state.operation_ids,
+            hierarchy: &mut hierarchy,
+            folder_events: &mut *state.folder_events,
+            variable_sources: &mut variable_sources,
+            folder_security: &mut folder_security,
+            webhooks: &mut *state.webhooks,
+            certificates: &mut *state.certificates,
         };
+        self.transform(&mut webhook_state, items);
+
+        for (path, mut item) in oas.paths {
+            let mut name = None;
+            for op in operations_mut(&mut item) {
+                // Webhooks are sent by the API, so its own auth does not apply.
+                op.security = None;
+                op.tags = None;
+                name = name.or_else(|| op.operation_id.clone());
+            }
+            state.webhooks.push(Webhook {
+                name: name.unwrap_or_else(|| path.clone()),
+                path,
+                item,
+            });
+        }
     }
 
     fn transform_request(&self, state: &mut TranspileState, item: &postman::Items) {
@@ -202,21 +578,39 @@ impl<'a> Transpiler<'a> {
             Some(n) => n,
             None => "<request>",
         };
-        if let Some(r) = &item.request {
-            if let postman::RequestUnion::RequestClass(request) = r {
-                if let Some(postman::Url::UrlClass(u)) = &request.url {
-                    if let Some(postman::Host::StringArray(parts)) = &u.host {
-                        self.transform_server(state, u, parts);
-                    }
+        if let Some(postman::RequestUnion::RequestClass(request)) = &item.request {
+            if let Some(postman::Url::UrlClass(u)) = &request.url {
+                if let Some(postman::Host::StringArray(parts)) = &u.host {
+                    self.transform_server(state, u, parts);
+                }
 
-                    if let Some(postman::UrlPath::UnionArray(p)) = &u.path {
-                        self.transform_paths(state, item, request, name, u, p)
-                    }
+                if let Some(postman::UrlPath::UnionArray(p)) = &u.path {
+                    self.transform_paths(state, item, request, name, u, p)
                 }
             }
         }
     }
 
+    /// Records the ID and name of the Postman item that an object comes from, when source
+    /// annotations are enabled.
+    fn annotate_source(
+        &self,
+        extensions: &mut BTreeMap<String, serde_json::Value>,
+        id: &Option<String>,
+        name: &str,
+    ) {
+        if !self.options.source_annotations {
+            return;
+        }
+        if let Some(id) = id {
+            extensions.insert(
+                "x-postman-id".to_string(),
+                serde_json::Value::from(id.clone()),
+            );
+        }
+        extensions.insert("x-postman-name".to_string(), serde_json::Value::from(name));
+    }
+
     fn transform_server(
         &self,
         state: &mut TranspileState,
@@ -236,6 +630,7 @@ impl<'a> Transpiler<'a> {
                     url: server_url,
                     description: None,
                     variables: None,
+                    ..openapi3::Server::default()
                 };
                 s.push(server);
             }
@@ -284,6 +679,34 @@ impl<'a> Transpiler<'a> {
                 .insert(segments.clone(), openapi3::PathItem::default());
         }
 
+        // Auth is inherited from the nearest folder, then the collection.
+        let security = request
+            .auth
+            .as_ref()
+            .and_then(|auth| self.security_requirements(state.oas, auth))
+            .or_else(|| state.folder_security.last().cloned())
+            .filter(|security| Some(security) != state.oas.security.as_ref())
+            .filter(|security| !security.is_empty() || state.oas.security.is_some());
+
+        // Tags are renamed when folder names repeat, so the original names are used.
+        let folders = state
+            .hierarchy
+            .iter()
+            .map(|tag| {
+                let name = state
+                    .oas
+                    .tags
+                    .iter()
+                    .flatten()
+                    .find(|t| t.name == *tag)
+                    .and_then(|t| t.extensions.get("x-postman-name"))
+                    .and_then(|name| name.as_str())
+                    .unwrap_or(tag);
+                serde_json::Value::from(name)
+            })
+            .collect::<Vec<_>>();
+
+        let mut operation_id = None;
         if let Some(path) = state.oas.paths.get_mut(&segments) {
             // description must exist on a path
             let description = match extract_description(&request.description) {
@@ -307,6 +730,7 @@ impl<'a> Transpiler<'a> {
                     };
                 }
             }
+            self.add_query_param_examples(&mut op, &item.response);
 
             let mut content_type: Option<String> = None;
 
@@ -315,8 +739,7 @@ impl<'a> Transpiler<'a> {
                     .iter()
                     .find(|h| h.key.to_lowercase() == "content-type");
                 if let Some(t) = content_type_header {
-                    let content_type_parts: Vec<&str> = t.value.split(';').collect();
-                    content_type = Some(content_type_parts[0].to_string());
+                    content_type = media_type_essence(&t.value);
                 }
             }
 
@@ -331,14 +754,41 @@ impl<'a> Transpiler<'a> {
                 op.tags = Some(state.hierarchy.clone());
             }
 
+            op.security = security;
+
+            if self.options.source_annotations {
+                self.annotate_source(&mut op.extensions, &item.id, request_name);
+                if !folders.is_empty() {
+                    op.extensions.insert(
+                        "x-postman-path".to_string(),
+                        serde_json::Value::Array(folders),
+                    );
+                }
+                if let Some(raw) = &url.raw {
+                    op.extensions.insert(
+                        "x-postman-url".to_string(),
+                        serde_json::Value::from(raw.clone()),
+                    );
+                }
+            }
+
+            let mut events = state.folder_events.clone();
+            events.extend(postman_events(&item.event, None));
+            if !events.is_empty() {
+                op.extensions.insert(
+                    "x-postman-events".to_string(),
+                    serde_json::Value::Array(events),
+                );
+            }
+
             if let Some(responses) = &item.response {
+                let mut descriptions = BTreeMap::<String, Vec<String>>::new();
                 for r in responses.iter() {
                     let mut oas_response = openapi3::Response::default();
                     let mut response_media_types = BTreeMap::<String, openapi3::MediaType>::new();
                     if let Some(res) = r {
-                        if let Some(name) = &res.name {
-                            oas_response.description = Some(name.clone());
-                        }
+                        oas_response.description = Some(self.response_description(res));
+                        let mut declared_content_type: Option<String> = None;
                         if let Some(postman::Headers::UnionArray(headers)) = &res.header {
                             let mut oas_headers = BTreeMap::<
                                 String,
@@ -346,17 +796,24 @@ impl<'a> Transpiler<'a> {
                             >::new();
                             for h in headers {
                                 if let postman::HeaderElement::Header(hdr) = h {
-                                    if hdr.value.is_empty()
-                                        || hdr.key.to_lowercase() == "content-type"
-                                    {
+                                    if hdr.key.to_lowercase() == "content-type" {
+                                        declared_content_type = media_type_essence(&hdr.value);
+                                        continue;
+                                    }
+                                    if hdr.value.is_empty() {
                                         continue;
                                     }
-                                    let mut oas_header = openapi3::Header::default();
-                                    let mut header_schema = openapi3::Schema::default();
-                                    header_schema.schema_type = Some("string".to_string());
-                                    header_schema.example =
-                                        Some(serde_json::Value::String(hdr.value.to_string()));
-                                    oas_header.schema = Some(header_schema);
+                                    let header_schema = openapi3::Schema {
+                                        schema_type: Some("string".to_string()),
+                                        example: Some(serde_json::Value::String(
+                                            self.redact_field(&hdr.key, &hdr.value),
+                                        )),
+                                        ..openapi3::Schema::default()
+                                    };
+                                    let oas_header = openapi3::Header {
+                                        schema: Some(header_schema),
+                                        ..openapi3::Header::default()
+                                    };
 
                                     oas_headers.insert(
                                         hdr.key.clone(),
@@ -370,47 +827,30 @@ impl<'a> Transpiler<'a> {
                         }
                         let mut response_content = openapi3::MediaType::default();
                         if let Some(raw) = &res.body {
-                            let mut response_content_type: Option<String> = None;
-                            let resolved_body = self.resolve_variables(&raw, VAR_REPLACE_CREDITS);
-                            let example_val;
-
-                            match serde_json::from_str(&resolved_body) {
-                                Ok(v) => match v {
-                                    serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
-                                        response_content_type =
-                                            Some("application/json".to_string());
-                                        if let Some(schema) = self.generate_schema(&v) {
-                                            response_content.schema =
-                                                Some(openapi3::ObjectOrReference::Object(schema));
-                                        }
-                                        example_val = v;
-                                    }
-                                    _ => {
-                                        example_val = serde_json::Value::String(resolved_body);
-                                    }
-                                },
-                                _ => {
-                                    // TODO: Check if XML, HTML, JavaScript
-                                    response_content_type = Some("text/plain".to_string());
-                                    example_val = serde_json::Value::String(resolved_body);
-                                }
+                            let (response_content_type, schema, example_val) =
+                                self.extract_raw_body(raw, declared_content_type);
+                            if let Some(schema) = schema {
+                                response_content.schema =
+                                    Some(openapi3::ObjectOrReference::Object(schema));
                             }
                             let mut example_map = BTreeMap::<
                                 String,
                                 openapi3::ObjectOrReference<openapi3::Example>,
                             >::new();
 
-                            let ex = openapi3::Example {
-                                summary: None,
-                                description: None,
-                                value: Some(example_val),
-                            };
-
                             let example_name = match &res.name {
                                 Some(n) => n.to_string(),
                                 None => "".to_string(),
                             };
 
+                            let mut ex = openapi3::Example {
+                                summary: None,
+                                description: None,
+                                value: Some(example_val),
+                                ..openapi3::Example::default()
+                            };
+                            self.annotate_source(&mut ex.extensions, &res.id, &example_name);
+
                             example_map
                                 .insert(example_name, openapi3::ObjectOrReference::Object(ex));
                             let example = openapi3::MediaTypeExample::Examples {
@@ -419,23 +859,46 @@ impl<'a> Transpiler<'a> {
 
                             response_content.examples = Some(example);
 
-                            if response_content_type.is_none() {
-                                response_content_type =
-                                    Some("application/octet-stream".to_string());
-                            }
-
-                            response_media_types.insert(
-                                response_content_type.unwrap().to_string(),
-                                response_content,
-                            );
+                            response_media_types.insert(response_content_type, response_content);
                         }
                         oas_response.content = Some(response_media_types);
                         if let Some(code) = &res.code {
-                            op.responses.insert(code.to_string(), oas_response);
+                            let code_descriptions =
+                                descriptions.entry(code.to_string()).or_default();
+                            if let Some(description) = &oas_response.description {
+                                if !code_descriptions.contains(description) {
+                                    code_descriptions.push(description.clone());
+                                }
+                            }
+                            // Saved examples sharing a status are merged into one response.
+                            match op.responses.entry(code.to_string()) {
+                                Entry::Occupied(mut existing) => {
+                                    self.merge_responses(existing.get_mut(), oas_response)
+                                }
+                                Entry::Vacant(vacant) => {
+                                    vacant.insert(oas_response);
+                                }
+                            }
                         }
                     }
                 }
+                for (code, code_descriptions) in descriptions {
+                    let description = code_descriptions
+                        .into_iter()
+                        .filter(|d| !d.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" | ");
+                    if let Some(response) = op.responses.get_mut(&code) {
+                        if !description.is_empty() {
+                            response.description = Some(description);
+                        }
+                    }
+                }
+            }
+            if op.responses.is_empty() {
+                self.infer_script_responses(item, &mut op);
             }
+
             if !op.responses.contains_key("200")
                 && !op.responses.contains_key("201")
                 && !op.responses.contains_key("202")
@@ -450,7 +913,7 @@ impl<'a> Transpiler<'a> {
                 op.responses.insert(
                     "200".to_string(),
                     openapi3::Response {
-                        description: Some("".to_string()),
+                        description: Some(reason_phrase(200).to_string()),
                         ..openapi3::Response::default()
                     },
                 );
@@ -477,6 +940,7 @@ impl<'a> Transpiler<'a> {
                     }
                 }
 
+                operation_id = Some(op_id.clone());
                 op.operation_id = Some(op_id);
                 match m.as_str() {
                     "get" => {
@@ -504,6 +968,93 @@ impl<'a> Transpiler<'a> {
                 }
             }
         }
+
+        if let Some(operation_id) = operation_id {
+            self.link_variables(state, item, &segments, &url.variable, &operation_id);
+            if let Some(certificate) = &request.certificate {
+                state
+                    .certificates
+                    .push((operation_id, certificate.name.clone()));
+            }
+        }
+    }
+
+    /// Links the responses of earlier requests that store a value in a variable to this
+    /// operation when it uses the variable as a path parameter, then records the variables
+    /// stored by this request's test scripts.
+    fn link_variables(
+        &self,
+        state: &mut TranspileState,
+        item: &postman::Items,
+        path: &str,
+        url_variables: &Option<Vec<postman::Variable>>,
+        operation_id: &str,
+    ) {
+        for cap in URI_TEMPLATE_VARIABLE_RE.captures_iter(path) {
+            let parameter = &cap[1];
+            // A path variable such as `:id` may take its value from another variable.
+            let variable = url_variables
+                .iter()
+                .flatten()
+                .find(|v| v.key.as_deref() == Some(parameter))
+                .and_then(|v| v.value.as_ref()?.as_str())
+                .map(str::trim)
+                .filter(|value| value.starts_with("{{") && value.ends_with("}}"))
+                .map_or(parameter, |value| &value[2..value.len() - 2]);
+            let source = match state.variable_sources.get(variable) {
+                Some(source) => source,
+                None => continue,
+            };
+
+            for path in state.oas.paths.values_mut() {
+                for op in operations_mut(path) {
+                    if op.operation_id.as_deref() != Some(&source.operation_id) {
+                        continue;
+                    }
+                    for (_, response) in op
+                        .responses
+                        .iter_mut()
+                        .filter(|(code, _)| code.starts_with('2'))
+                    {
+                        let link = response
+                            .links
+                            .get_or_insert_with(BTreeMap::new)
+                            .entry(operation_id.to_string())
+                            .or_insert_with(|| {
+                                openapi3::ObjectOrReference::Object(openapi3::Link::Id {
+                                    operation_id: operation_id.to_string(),
+                                    parameters: None,
+                                    request_body: None,
+                                    description: None,
+                                    server: None,
+                                    extensions: BTreeMap::new(),
+                                })
+                            });
+                        if let openapi3::ObjectOrReference::Object(openapi3::Link::Id {
+                            parameters,
+                            ..
+                        }) = link
+                        {
+                            parameters.get_or_insert_with(BTreeMap::new).insert(
+                                parameter.to_string(),
+                                serde_json::Value::String(source.expression.clone()),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        let script = scripts::test_script(&item.event);
+        for (variable, expression) in scripts::variable_assignments(&script) {
+            state.variable_sources.insert(
+                variable,
+                VariableSource {
+                    operation_id: operation_id.to_string(),
+                    expression,
+                },
+            );
+        }
     }
 
     fn extract_request_body(
@@ -519,31 +1070,13 @@ impl<'a> Transpiler<'a> {
         if let Some(mode) = &body.mode {
             match mode {
                 postman::Mode::Raw => {
-                    content_type = Some("application/octet-stream".to_string());
                     if let Some(raw) = &body.raw {
-                        let resolved_body = self.resolve_variables(&raw, VAR_REPLACE_CREDITS);
-                        let example_val;
-
                         //set content type based on options or inference.
-                        match serde_json::from_str(&resolved_body) {
-                            Ok(v) => match v {
-                                serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
-                                    content_type = Some("application/json".to_string());
-                                    if let Some(schema) = self.generate_schema(&v) {
-                                        content.schema =
-                                            Some(openapi3::ObjectOrReference::Object(schema));
-                                    }
-                                    example_val = v;
-                                }
-                                _ => {
-                                    example_val = serde_json::Value::String(resolved_body);
-                                }
-                            },
-                            _ => {
-                                // TODO: Check if XML, HTML, JavaScript
-                                content_type = Some("text/plain".to_string());
-                                example_val = serde_json::Value::String(resolved_body);
-                            }
+                        let (raw_content_type, schema, example_val) =
+                            self.extract_raw_body(raw, content_type.take());
+                        content_type = Some(raw_content_type);
+                        if let Some(schema) = schema {
+                            content.schema = Some(openapi3::ObjectOrReference::Object(schema));
                         }
 
                         let example = openapi3::MediaTypeExample::Example {
@@ -558,56 +1091,131 @@ impl<'a> Transpiler<'a> {
                         let mut oas_data = serde_json::Map::new();
                         for i in urlencoded {
                             if let Some(v) = &i.value {
-                                let value = serde_json::Value::String(v.to_string());
+                                let value = serde_json::Value::String(self.redact_field(&i.key, v));
                                 oas_data.insert(i.key.clone(), value);
                             }
                         }
                         let oas_obj = serde_json::Value::Object(oas_data);
-                        if let Some(schema) = self.generate_schema(&oas_obj) {
+                        if let Some(schema) = self.generate_body_schema(&oas_obj) {
                             content.schema = Some(openapi3::ObjectOrReference::Object(schema));
                         }
-                        let example = openapi3::MediaTypeExample::Example { example: oas_obj };
+                        let example = openapi3::MediaTypeExample::Example {
+                            example: self.trim_example(oas_obj),
+                        };
                         content.examples = Some(example);
                     }
                 }
                 postman::Mode::Formdata => {
                     content_type = Some("multipart/form-data".to_string());
-                    let mut schema = openapi3::Schema::default();
-                    schema.schema_type = Some("object".to_string());
+                    let mut schema = openapi3::Schema {
+                        schema_type: Some("object".to_string()),
+                        ..openapi3::Schema::default()
+                    };
                     let mut properties = BTreeMap::<String, openapi3::Schema>::new();
+                    let mut occurrences = BTreeMap::<&str, usize>::new();
+                    let mut part_types = BTreeMap::<&str, Vec<String>>::new();
 
                     if let Some(formdata) = &body.formdata {
                         for i in formdata {
-                            if let Some(t) = &i.form_parameter_type {
-                                let is_binary = t.as_str() == "file";
-                                if let Some(v) = &i.value {
-                                    let value = serde_json::Value::String(v.to_string());
-                                    let prop_schema = self.generate_schema(&value);
-                                    if let Some(mut prop_schema) = prop_schema {
-                                        if is_binary {
-                                            prop_schema.format = Some("binary".to_string());
-                                        }
-                                        prop_schema.description =
-                                            extract_description(&i.description);
-                                        properties.insert(i.key.clone(), prop_schema);
-                                    }
-                                } else {
-                                    let mut prop_schema = openapi3::Schema::default();
-                                    prop_schema.schema_type = Some("string".to_string());
-                                    prop_schema.description = extract_description(&i.description);
-                                    if is_binary {
-                                        prop_schema.format = Some("binary".to_string());
-                                    }
-                                    properties.insert(i.key.clone(), prop_schema);
+                            let is_binary = match &i.form_parameter_type {
+                                Some(t) => t.as_str() == "file",
+                                None => continue,
+                            };
+                            let sources = match &i.src {
+                                Some(postman::Host::String(src)) => vec![src.as_str()],
+                                Some(postman::Host::StringArray(srcs)) => {
+                                    srcs.iter().map(|src| src.as_str()).collect()
+                                }
+                                None => vec![],
+                            };
+                            *occurrences.entry(&i.key).or_insert(0) += sources.len().max(1);
+
+                            let types = part_types.entry(&i.key).or_default();
+                            let declared = i.content_type.iter().map(|t| t.trim().to_string());
+                            let inferred = sources
+                                .iter()
+                                .filter(|_| is_binary && i.content_type.is_none())
+                                .filter_map(|src| file_media_type(src))
+                                .map(|t| t.to_string());
+                            for part_type in declared.chain(inferred) {
+                                if !types.contains(&part_type) {
+                                    types.push(part_type);
                                 }
                             }
-                            // NOTE: Postman doesn't store the content type of multipart files. :(
+
+                            let prop_schema = self.generate_form_part_schema(i, is_binary);
+                            let prop_schema = match properties.remove(&i.key) {
+                                Some(original) => self.merge_schemas(original, &prop_schema),
+                                None => prop_schema,
+                            };
+                            properties.insert(i.key.clone(), prop_schema);
+                        }
+
+                        // Repeated keys send several parts under the same name.
+                        for (key, count) in occurrences {
+                            if count < 2 {
+                                continue;
+                            }
+                            if let Some(mut items) = properties.remove(key) {
+                                let description = items.description.take();
+                                properties.insert(
+                                    key.to_string(),
+                                    openapi3::Schema {
+                                        schema_type: Some("array".to_string()),
+                                        description,
+                                        items: Some(Box::new(items)),
+                                        ..openapi3::Schema::default()
+                                    },
+                                );
+                            }
+                        }
+                        let encoding = part_types
+                            .into_iter()
+                            .filter(|(_, types)| !types.is_empty())
+                            .map(|(key, types)| {
+                                let encoding = openapi3::Encoding {
+                                    content_type: Some(types.join(", ")),
+                                    ..openapi3::Encoding::default()
+                                };
+                                (key.to_string(), encoding)
+                            })
+                            .collect::<BTreeMap<_, _>>();
+                        if !encoding.is_empty() {
+                            content.encoding = Some(encoding);
                         }
+
                         schema.properties = Some(properties);
                         content.schema = Some(openapi3::ObjectOrReference::Object(schema));
                     }
                 }
-                _ => content_type = Some("application/octet-stream".to_string()),
+                postman::Mode::Graphql => {
+                    content_type = Some("application/json".to_string());
+                    if let Some(graphql) = &body.graphql {
+                        let (schema, example) = self.extract_graphql_body(graphql);
+                        content.schema = Some(openapi3::ObjectOrReference::Object(schema));
+                        content.examples = Some(openapi3::MediaTypeExample::Example { example });
+                    }
+                }
+                postman::Mode::File => {
+                    let file = body.file.as_ref();
+                    if content_type.is_none() {
+                        content_type = file
+                            .and_then(|f| f.src.as_deref())
+                            .and_then(file_media_type)
+                            .map(|t| t.to_string());
+                    }
+                    content.schema = Some(openapi3::ObjectOrReference::Object(openapi3::Schema {
+                        schema_type: Some("string".to_string()),
+                        format: Some("binary".to_string()),
+                        ..openapi3::Schema::default()
+                    }));
+                    if let Some(text) = file.and_then(|f| f.content.as_ref()) {
+                        let example = serde_json::Value::String(self.redact_text(text));
+                        content.examples = Some(openapi3::MediaTypeExample::Example {
+                            example: self.trim_example(example),
+                        });
+                    }
+                }
             }
         }
 
@@ -644,7 +1252,7 @@ impl<'a> Transpiler<'a> {
                         if let Some(v2) = v.as_str() {
                             let re = regex::Regex::new(&regex::escape(&cap[0])).unwrap();
                             return self.resolve_variables(
-                                &re.replace_all(&s, v2).to_string(),
+                                re.replace_all(&s, v2).as_ref(),
                                 sub_replace_credits - 1,
                             );
                         }
@@ -659,8 +1267,10 @@ impl<'a> Transpiler<'a> {
     fn generate_schema(&self, value: &serde_json::Value) -> Option<openapi3::Schema> {
         match value {
             serde_json::Value::Object(m) => {
-                let mut schema = openapi3::Schema::default();
-                schema.schema_type = Some("object".to_string());
+                let mut schema = openapi3::Schema {
+                    schema_type: Some("object".to_string()),
+                    ..openapi3::Schema::default()
+                };
                 let mut properties = BTreeMap::<String, openapi3::Schema>::new();
 
                 for (key, val) in m.iter() {
@@ -673,8 +1283,10 @@ impl<'a> Transpiler<'a> {
                 Some(schema)
             }
             serde_json::Value::Array(a) => {
-                let mut schema = openapi3::Schema::default();
-                schema.schema_type = Some("array".to_string());
+                let mut schema = openapi3::Schema {
+                    schema_type: Some("array".to_string()),
+                    ..openapi3::Schema::default()
+                };
                 let mut item_schema = openapi3::Schema::default();
 
                 for n in 0..a.len() {
@@ -690,93 +1302,574 @@ impl<'a> Transpiler<'a> {
                 }
 
                 schema.items = Some(Box::new(item_schema));
-                schema.example = Some(value.clone());
+                schema.example = Some(self.trim_example(value.clone()));
 
                 Some(schema)
             }
             serde_json::Value::String(_) => {
-                let mut schema = openapi3::Schema::default();
-                schema.schema_type = Some("string".to_string());
-                schema.example = Some(value.clone());
+                let schema = openapi3::Schema {
+                    schema_type: Some("string".to_string()),
+                    example: Some(self.trim_example(value.clone())),
+                    ..openapi3::Schema::default()
+                };
                 Some(schema)
             }
             serde_json::Value::Number(_) => {
-                let mut schema = openapi3::Schema::default();
-                schema.schema_type = Some("number".to_string());
-                schema.example = Some(value.clone());
+                let schema = openapi3::Schema {
+                    schema_type: Some("number".to_string()),
+                    example: Some(value.clone()),
+                    ..openapi3::Schema::default()
+                };
                 Some(schema)
             }
             serde_json::Value::Bool(_) => {
-                let mut schema = openapi3::Schema::default();
-                schema.schema_type = Some("boolean".to_string());
-                schema.example = Some(value.clone());
+                let schema = openapi3::Schema {
+                    schema_type: Some("boolean".to_string()),
+                    example: Some(value.clone()),
+                    ..openapi3::Schema::default()
+                };
                 Some(schema)
             }
             serde_json::Value::Null => {
-                let mut schema = openapi3::Schema::default();
-                schema.nullable = Some(true);
-                schema.example = Some(value.clone());
+                let schema = openapi3::Schema {
+                    nullable: Some(true),
+                    example: Some(value.clone()),
+                    ..openapi3::Schema::default()
+                };
                 Some(schema)
             }
         }
     }
 
-    fn merge_schemas(
+    /// Determines the media type, schema and example for a raw request or response body. A
+    /// declared content type takes precedence over the type sniffed from the body.
+    fn extract_raw_body(
         &self,
-        mut original: openapi3::Schema,
-        new: &openapi3::Schema,
-    ) -> openapi3::Schema {
-        // If the new schema has a nullable Option but the original doesn't,
-        // set the original nullable to the new one.
-        if original.nullable.is_none() && new.nullable.is_some() {
-            original.nullable = new.nullable;
+        raw: &str,
+        declared_content_type: Option<String>,
+    ) -> (String, Option<openapi3::Schema>, serde_json::Value) {
+        let resolved_body = self.resolve_variables(raw, VAR_REPLACE_CREDITS);
+        let json = serde_json::from_str::<serde_json::Value>(&resolved_body)
+            .ok()
+            .map(|v| self.redact_value(v));
+        let is_structured = |v: &serde_json::Value| v.is_object() || v.is_array();
+
+        let content_type = match declared_content_type {
+            Some(t) => t,
+            None => match &json {
+                Some(v) if is_structured(v) => "application/json".to_string(),
+                Some(_) => "application/octet-stream".to_string(),
+                None => sniff_media_type(&resolved_body).to_string(),
+            },
+        };
+
+        if is_json_media_type(&content_type) {
+            if let Some(v) = json {
+                if is_structured(&v) {
+                    let schema = self.generate_body_schema(&v);
+                    return (content_type, schema, self.trim_example(v));
+                }
+            }
         }
 
-        // If both original and new have a nullable Option,
-        // If any of their values is true, set to true.
-        if let Some(original_nullable) = original.nullable {
-            if let Some(new_nullable) = new.nullable {
-                if new_nullable != original_nullable {
-                    original.nullable = Some(true);
+        let text = self.redact_text(&resolved_body);
+        let mut schema = None;
+        if is_xml_media_type(&content_type) {
+            schema = self.generate_xml_schema(&text);
+            if self.options.examples.omit_schema_examples {
+                if let Some(schema) = &mut schema {
+                    clear_schema_examples(schema);
                 }
             }
         }
+        let example = self.trim_example(serde_json::Value::String(text));
+        (content_type, schema, example)
+    }
 
-        if let Some(ref mut any_of) = original.any_of {
-            any_of.push(openapi3::ObjectOrReference::Object(new.clone()));
-            return original;
+    /// Generates the schema of a multipart form part. Text parts holding a JSON object or array
+    /// are described by the schema of that JSON rather than as strings.
+    fn generate_form_part_schema(
+        &self,
+        parameter: &postman::FormParameter,
+        is_binary: bool,
+    ) -> openapi3::Schema {
+        let json = parameter
+            .value
+            .as_ref()
+            .filter(|_| !is_binary)
+            .and_then(|v| serde_json::from_str::<serde_json::Value>(v).ok())
+            .filter(|v| v.is_object() || v.is_array());
+        let schema = match (json, &parameter.value) {
+            (Some(json), _) => self.generate_schema(&self.redact_value(json)),
+            (None, Some(v)) => self.generate_schema(&serde_json::Value::String(
+                self.redact_field(&parameter.key, v),
+            )),
+            (None, None) => None,
+        };
+        let mut schema = schema.unwrap_or_else(|| openapi3::Schema {
+            schema_type: Some("string".to_string()),
+            ..openapi3::Schema::default()
+        });
+        if is_binary {
+            schema.format = Some("binary".to_string());
         }
+        schema.description = extract_description(&parameter.description);
+        schema
+    }
 
-        // Reset the schema type.
-        if original.schema_type.is_none() && new.schema_type.is_some() && new.any_of.is_none() {
-            original.schema_type = new.schema_type.clone();
+    /// Determines the schema and example for a GraphQL request body, which holds the query, its
+    /// variables and the name of the operation to run.
+    fn extract_graphql_body(
+        &self,
+        graphql: &postman::GraphQlBody,
+    ) -> (openapi3::Schema, serde_json::Value) {
+        let query = graphql.query.clone().unwrap_or_default();
+        let variables = match &graphql.variables {
+            Some(serde_json::Value::String(text)) => {
+                serde_json::from_str(&self.resolve_variables(text, VAR_REPLACE_CREDITS)).ok()
+            }
+            variables => variables.clone(),
+        };
+
+        let mut example = serde_json::Map::new();
+        if let Some(operation) = GRAPHQL_OPERATION_RE.captures(&query) {
+            example.insert(
+                "operationName".to_string(),
+                serde_json::Value::String(operation[1].to_string()),
+            );
+        }
+        example.insert("query".to_string(), serde_json::Value::String(query));
+        if let Some(variables) = variables.filter(|v| v.is_object()) {
+            example.insert("variables".to_string(), self.redact_value(variables));
+        }
+        let example = serde_json::Value::Object(example);
+
+        let mut schema = self.generate_body_schema(&example).unwrap_or_default();
+        let properties = schema.properties.get_or_insert_with(BTreeMap::new);
+        for (name, schema_type) in &[("operationName", "string"), ("variables", "object")] {
+            properties
+                .entry(name.to_string())
+                .or_insert_with(|| openapi3::Schema {
+                    schema_type: Some(schema_type.to_string()),
+                    ..openapi3::Schema::default()
+                });
         }
+        schema.required = Some(vec!["query".to_string()]);
+        (schema, self.trim_example(example))
+    }
 
-        // If both types are objects, merge the schemas of each property.
-        if let Some(t) = &original.schema_type {
-            if let "object" = t.as_str() {
-                if let Some(original_properties) = &mut original.properties {
-                    if let Some(new_properties) = &new.properties {
-                        for (key, val) in original_properties.iter_mut() {
-                            if let Some(v) = new_properties.get(key) {
-                                let prop = v;
-                                *val = self.merge_schemas(val.clone(), &prop);
-                            }
-                        }
+    fn generate_xml_schema(&self, text: &str) -> Option<openapi3::Schema> {
+        let document = roxmltree::Document::parse(text).ok()?;
+        let root = document.root_element();
+        let mut schema = self.generate_xml_element_schema(root);
+        let mut xml = xml_metadata(root).unwrap_or_default();
+        xml.name = Some(root.tag_name().name().to_string());
+        schema.xml = Some(xml);
+        Some(schema)
+    }
 
-                        for (key, val) in new_properties.iter() {
-                            if !original_properties.contains_key(key) {
-                                original_properties.insert(key.to_string(), val.clone());
-                            }
-                        }
-                    }
-                }
-            }
+    fn generate_xml_element_schema(&self, node: roxmltree::Node) -> openapi3::Schema {
+        let children = node
+            .children()
+            .filter(|n| n.is_element())
+            .collect::<Vec<_>>();
+
+        if children.is_empty() && node.attributes().is_empty() {
+            let text = node.text().unwrap_or_default().trim();
+            let value = match serde_json::from_str::<serde_json::Value>(text) {
+                Ok(v) if v.is_number() || v.is_boolean() => v,
+                _ => serde_json::Value::String(text.to_string()),
+            };
+            return self.generate_schema(&value).unwrap_or_default();
         }
 
-        if let Some(ref original_type) = original.schema_type {
-            if let Some(ref new_type) = new.schema_type {
-                if new_type != original_type {
+        let mut properties = BTreeMap::<String, openapi3::Schema>::new();
+        for attribute in node.attributes() {
+            let mut schema = self
+                .generate_schema(&serde_json::Value::String(
+                    self.redact_field(attribute.name(), attribute.value()),
+                ))
+                .unwrap_or_default();
+            schema.xml = Some(openapi3::Xml {
+                attribute: Some(true),
+                namespace: attribute.namespace().map(|ns| ns.to_string()),
+                ..openapi3::Xml::default()
+            });
+            properties.insert(attribute.name().to_string(), schema);
+        }
+
+        let mut occurrences = BTreeMap::<&str, usize>::new();
+        for child in &children {
+            *occurrences.entry(child.tag_name().name()).or_insert(0) += 1;
+        }
+
+        let mut elements = BTreeMap::<String, openapi3::Schema>::new();
+        for child in children {
+            let name = child.tag_name().name();
+            let mut schema = self.generate_xml_element_schema(child);
+            schema.xml = xml_metadata(child);
+            let schema = match elements.remove(name) {
+                Some(original) => self.merge_schemas(original, &schema),
+                None => schema,
+            };
+            elements.insert(name.to_string(), schema);
+        }
+
+        for (name, mut schema) in elements {
+            if occurrences.get(name.as_str()).cloned().unwrap_or(0) > 1 {
+                let mut xml = schema.xml.take().unwrap_or_default();
+                xml.name = Some(name.clone());
+                schema.xml = Some(xml);
+                schema = openapi3::Schema {
+                    schema_type: Some("array".to_string()),
+                    items: Some(Box::new(schema)),
+                    ..openapi3::Schema::default()
+                };
+            }
+            properties.insert(name, schema);
+        }
+
+        openapi3::Schema {
+            schema_type: Some("object".to_string()),
+            properties: Some(properties),
+            ..openapi3::Schema::default()
+        }
+    }
+
+    /// Generates the schema for a media type that carries its own example.
+    fn generate_body_schema(&self, value: &serde_json::Value) -> Option<openapi3::Schema> {
+        let mut schema = self.generate_schema(value)?;
+        if self.options.examples.omit_schema_examples {
+            clear_schema_examples(&mut schema);
+        }
+        Some(schema)
+    }
+
+    fn is_secret_key(&self, key: &str) -> bool {
+        let redaction = &self.options.redaction;
+        (redaction.default_rules && SECRET_KEY_RE.is_match(key))
+            || redaction.keys.iter().any(|k| k.eq_ignore_ascii_case(key))
+    }
+
+    fn redact_text(&self, text: &str) -> String {
+        let redaction = &self.options.redaction;
+        let default_patterns: &[regex::Regex] = if redaction.default_rules {
+            &SECRET_VALUE_RES
+        } else {
+            &[]
+        };
+        default_patterns
+            .iter()
+            .chain(redaction.patterns.iter())
+            .fold(text.to_string(), |text, re| {
+                re.replace_all(&text, REDACTED).to_string()
+            })
+    }
+
+    fn redact_field(&self, key: &str, value: &str) -> String {
+        if self.is_secret_key(key) && !value.is_empty() {
+            REDACTED.to_string()
+        } else {
+            self.redact_text(value)
+        }
+    }
+
+    fn redact_value(&self, value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(m) => serde_json::Value::Object(
+                m.into_iter()
+                    .map(|(k, v)| match v {
+                        serde_json::Value::Null => (k, v),
+                        // Numbers, objects and arrays under a secret key are replaced wholesale.
+                        _ if self.is_secret_key(&k) => {
+                            (k, serde_json::Value::String(REDACTED.to_string()))
+                        }
+                        v => (k, self.redact_value(v)),
+                    })
+                    .collect(),
+            ),
+            serde_json::Value::Array(a) => {
+                serde_json::Value::Array(a.into_iter().map(|v| self.redact_value(v)).collect())
+            }
+            serde_json::Value::String(s) => serde_json::Value::String(self.redact_text(&s)),
+            v => v,
+        }
+    }
+
+    fn trim_example(&self, value: serde_json::Value) -> serde_json::Value {
+        let options = &self.options.examples;
+        match value {
+            serde_json::Value::Array(a) => {
+                let len = options.max_array_length.unwrap_or(a.len());
+                serde_json::Value::Array(
+                    a.into_iter()
+                        .take(len)
+                        .map(|v| self.trim_example(v))
+                        .collect(),
+                )
+            }
+            serde_json::Value::Object(m) => serde_json::Value::Object(
+                m.into_iter()
+                    .map(|(k, v)| (k, self.trim_example(v)))
+                    .collect(),
+            ),
+            serde_json::Value::String(s) => match options.max_string_length {
+                Some(len) if s.chars().count() > len => {
+                    serde_json::Value::String(s.chars().take(len).collect())
+                }
+                _ => serde_json::Value::String(s),
+            },
+            v => v,
+        }
+    }
+
+    fn response_description(&self, res: &postman::ResponseClass) -> String {
+        let name = res.name.as_deref().map(str::trim);
+        // Status text is sometimes saved together with the code, e.g. "404 Not Found".
+        let status = res
+            .status
+            .as_deref()
+            .map(|s| s.trim_start_matches(|c: char| c.is_ascii_digit()).trim());
+        let reason = res.code.map(reason_phrase);
+        let sources = match self.options.response_description {
+            ResponseDescription::ExampleName => [name, status, reason],
+            ResponseDescription::StatusText => [status, reason, name],
+            ResponseDescription::ReasonPhrase => [reason, status, name],
+        };
+        sources
+            .iter()
+            .flatten()
+            .find(|d| !d.is_empty())
+            .map_or_else(String::new, |d| d.to_string())
+    }
+
+    /// Builds response entries from the status codes, content types and body paths asserted by
+    /// the test scripts of a request without saved examples.
+    fn infer_script_responses(&self, item: &postman::Items, op: &mut openapi3::Operation) {
+        let assertions = scripts::response_assertions(&scripts::test_script(&item.event));
+
+        let mut schema: Option<openapi3::Schema> = None;
+        for (path, value) in &assertions.json_paths {
+            self.add_json_path(
+                schema.get_or_insert_with(openapi3::Schema::default),
+                path,
+                value.as_ref(),
+            );
+        }
+
+        let mut status_codes = assertions.status_codes;
+        if status_codes.is_empty() && !assertions.content_types.is_empty() {
+            status_codes.push(200);
+        }
+        for code in status_codes {
+            let content = match code {
+                204 | 304 => BTreeMap::new(),
+                _ => assertions
+                    .content_types
+                    .iter()
+                    .map(|content_type| {
+                        let media_type = openapi3::MediaType {
+                            schema: schema.clone().map(openapi3::ObjectOrReference::Object),
+                            ..openapi3::MediaType::default()
+                        };
+                        (content_type.clone(), media_type)
+                    })
+                    .collect(),
+            };
+            op.responses.insert(
+                code.to_string(),
+                openapi3::Response {
+                    description: Some(reason_phrase(code).to_string()),
+                    content: if content.is_empty() {
+                        None
+                    } else {
+                        Some(content)
+                    },
+                    ..openapi3::Response::default()
+                },
+            );
+        }
+    }
+
+    fn add_json_path(
+        &self,
+        schema: &mut openapi3::Schema,
+        path: &[scripts::JsonPathSegment],
+        value: Option<&serde_json::Value>,
+    ) {
+        match path.split_first() {
+            None => {
+                if let Some(value) = value {
+                    if schema.schema_type.is_none() {
+                        if let Some(leaf) = self.generate_schema(&self.redact_value(value.clone()))
+                        {
+                            *schema = leaf;
+                        }
+                    }
+                }
+            }
+            Some((scripts::JsonPathSegment::Index, rest)) => {
+                if schema
+                    .schema_type
+                    .get_or_insert_with(|| "array".to_string())
+                    == "array"
+                {
+                    let items = schema
+                        .items
+                        .get_or_insert_with(|| Box::new(openapi3::Schema::default()));
+                    self.add_json_path(items, rest, value);
+                }
+            }
+            Some((scripts::JsonPathSegment::Property(name), rest)) => {
+                if schema
+                    .schema_type
+                    .get_or_insert_with(|| "object".to_string())
+                    == "object"
+                {
+                    let property = schema
+                        .properties
+                        .get_or_insert_with(BTreeMap::new)
+                        .entry(name.clone())
+                        .or_default();
+                    self.add_json_path(property, rest, value);
+                }
+            }
+        }
+    }
+
+    /// Merges a saved response into another one with the same status code. The caller joins the
+    /// descriptions.
+    fn merge_responses(&self, existing: &mut openapi3::Response, new: openapi3::Response) {
+        if let Some(headers) = new.headers {
+            let existing_headers = existing.headers.get_or_insert_with(BTreeMap::new);
+            for (name, header) in headers {
+                existing_headers.entry(name).or_insert(header);
+            }
+        }
+
+        if let Some(content) = new.content {
+            let existing_content = existing.content.get_or_insert_with(BTreeMap::new);
+            for (media_type, new_media_type) in content {
+                match existing_content.entry(media_type) {
+                    Entry::Occupied(mut media_type) => {
+                        self.merge_media_types(media_type.get_mut(), new_media_type)
+                    }
+                    Entry::Vacant(vacant) => {
+                        vacant.insert(new_media_type);
+                    }
+                }
+            }
+        }
+    }
+
+    fn merge_media_types(&self, media_type: &mut openapi3::MediaType, new: openapi3::MediaType) {
+        media_type.schema = match (media_type.schema.take(), new.schema) {
+            (
+                Some(openapi3::ObjectOrReference::Object(original)),
+                Some(openapi3::ObjectOrReference::Object(schema)),
+            ) => Some(openapi3::ObjectOrReference::Object(
+                self.merge_schemas(original, &schema),
+            )),
+            (original, schema) => original.or(schema),
+        };
+
+        media_type.examples = match (media_type.examples.take(), new.examples) {
+            (Some(original), Some(examples)) => {
+                let mut merged = named_examples(original);
+                for (name, example) in named_examples(examples) {
+                    let name = unique_example_name(&merged, name);
+                    merged.insert(name, example);
+                }
+                Some(openapi3::MediaTypeExample::Examples { examples: merged })
+            }
+            (original, examples) => original.or(examples),
+        };
+    }
+
+    fn merge_schemas(
+        &self,
+        mut original: openapi3::Schema,
+        new: &openapi3::Schema,
+    ) -> openapi3::Schema {
+        // If the new schema has a nullable Option but the original doesn't,
+        // set the original nullable to the new one.
+        if original.nullable.is_none() && new.nullable.is_some() {
+            original.nullable = new.nullable;
+        }
+
+        // If both original and new have a nullable Option,
+        // If any of their values is true, set to true.
+        if let Some(original_nullable) = original.nullable {
+            if let Some(new_nullable) = new.nullable {
+                if new_nullable != original_nullable {
+                    original.nullable = Some(true);
+                }
+            }
+        }
+
+        if let Some(ref mut any_of) = original.any_of {
+            any_of.push(openapi3::ObjectOrReference::Object(new.clone()));
+            return original;
+        }
+
+        if original.one_of.is_some() {
+            return self.merge_one_of(original, new);
+        }
+
+        // Objects of different shapes sharing a constant-valued property become
+        // tagged variants of a oneOf.
+        if let Some(property_name) = find_discriminator(&original, new) {
+            let nullable = original.nullable;
+            return openapi3::Schema {
+                nullable,
+                one_of: Some(vec![
+                    openapi3::ObjectOrReference::Object(discriminated_variant(
+                        original,
+                        &property_name,
+                    )),
+                    openapi3::ObjectOrReference::Object(discriminated_variant(
+                        new.clone(),
+                        &property_name,
+                    )),
+                ]),
+                discriminator: Some(openapi3::Discriminator {
+                    property_name,
+                    mapping: None,
+                }),
+                ..openapi3::Schema::default()
+            };
+        }
+
+        // Reset the schema type.
+        if original.schema_type.is_none() && new.schema_type.is_some() && new.any_of.is_none() {
+            original.schema_type = new.schema_type.clone();
+        }
+
+        // If both types are objects, merge the schemas of each property.
+        if let Some(t) = &original.schema_type {
+            if let "object" = t.as_str() {
+                if let Some(original_properties) = &mut original.properties {
+                    if let Some(new_properties) = &new.properties {
+                        for (key, val) in original_properties.iter_mut() {
+                            if let Some(v) = new_properties.get(key) {
+                                let prop = v;
+                                *val = self.merge_schemas(val.clone(), prop);
+                            }
+                        }
+
+                        for (key, val) in new_properties.iter() {
+                            if !original_properties.contains_key(key) {
+                                original_properties.insert(key.to_string(), val.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(ref original_type) = original.schema_type {
+            if let Some(ref new_type) = new.schema_type {
+                if new_type != original_type {
                     let cloned = original.clone();
                     original.schema_type = None;
                     original.properties = None;
@@ -792,6 +1885,237 @@ impl<'a> Transpiler<'a> {
         original
     }
 
+    fn merge_one_of(
+        &self,
+        mut original: openapi3::Schema,
+        new: &openapi3::Schema,
+    ) -> openapi3::Schema {
+        // Nullability has already been merged into the parent schema.
+        if new.schema_type.is_none() && new.one_of.is_none() && new.any_of.is_none() {
+            return original;
+        }
+
+        let property_name = original
+            .discriminator
+            .as_ref()
+            .map(|d| d.property_name.clone());
+
+        if let Some(ref mut one_of) = original.one_of {
+            match property_name {
+                Some(property_name) => {
+                    let value = discriminator_value(new, &property_name);
+                    if value.is_some() {
+                        for variant in one_of.iter_mut() {
+                            if let openapi3::ObjectOrReference::Object(v) = variant {
+                                if discriminator_value(v, &property_name) == value {
+                                    *v = self.merge_schemas(v.clone(), new);
+                                    return original;
+                                }
+                            }
+                        }
+                    }
+                    one_of.push(openapi3::ObjectOrReference::Object(discriminated_variant(
+                        new.clone(),
+                        &property_name,
+                    )));
+                }
+                None => one_of.push(openapi3::ObjectOrReference::Object(new.clone())),
+            }
+        }
+
+        original
+    }
+
+    /// Declares the security scheme for a Postman auth in `components.securitySchemes` and
+    /// returns the requirement for it. `noauth` yields no requirements, which removes any
+    /// inherited security from an operation. Auth that cannot be described, such as an OAuth 2.0
+    /// flow whose URLs are unresolved variables, yields `None` and is treated as absent.
+    fn security_requirements(
+        &self,
+        oas: &mut openapi3::Spec,
+        auth: &postman::Auth,
+    ) -> Option<Vec<openapi3::SecurityRequirement>> {
+        if matches!(auth.auth_type, postman::AuthType::Noauth) {
+            return Some(vec![]);
+        }
+        let (name, scheme, scopes) = self.security_scheme(auth)?;
+        let schemes = oas
+            .components
+            .get_or_insert_with(Default::default)
+            .security_schemes
+            .get_or_insert_with(BTreeMap::new);
+
+        // Different configurations of the same auth type get numbered scheme names.
+        let mut scheme_name = name.to_string();
+        let mut i = 1;
+        loop {
+            match schemes.get(&scheme_name) {
+                None => {
+                    schemes.insert(
+                        scheme_name.clone(),
+                        openapi3::ObjectOrReference::Object(scheme),
+                    );
+                    break;
+                }
+                Some(openapi3::ObjectOrReference::Object(existing)) if *existing == scheme => break,
+                _ => {
+                    i += 1;
+                    scheme_name = format!("{}{}", name, i);
+                }
+            }
+        }
+
+        let mut requirement = openapi3::SecurityRequirement::new();
+        requirement.insert(scheme_name, scopes);
+        Some(vec![requirement])
+    }
+
+    /// Returns the name, security scheme and required scopes for a Postman auth.
+    fn security_scheme(
+        &self,
+        auth: &postman::Auth,
+    ) -> Option<(&'static str, openapi3::SecurityScheme, Vec<String>)> {
+        let attributes = match auth.auth_type {
+            postman::AuthType::Apikey => &auth.apikey,
+            postman::AuthType::Awsv4 => &auth.awsv4,
+            postman::AuthType::Basic => &auth.basic,
+            postman::AuthType::Bearer => &auth.bearer,
+            postman::AuthType::Digest => &auth.digest,
+            postman::AuthType::Hawk => &auth.hawk,
+            postman::AuthType::Ntlm => &auth.ntlm,
+            postman::AuthType::Oauth1 => &auth.oauth1,
+            postman::AuthType::Oauth2 => &auth.oauth2,
+            postman::AuthType::Noauth => return None,
+        };
+        let attribute = |key: &str| {
+            attributes
+                .as_ref()
+                .and_then(|attributes| auth_attribute(attributes, key))
+                .map(|value| self.resolve_variables(&value, VAR_REPLACE_CREDITS))
+                .filter(|value| !value.is_empty())
+        };
+        let http = |scheme: &str| openapi3::SecurityScheme::Http {
+            scheme: scheme.to_string(),
+            bearer_format: None,
+            description: None,
+            extensions: BTreeMap::new(),
+        };
+        // Signature-based schemes have no OpenAPI equivalent; they are sent in the
+        // Authorization header.
+        let authorization_header = |description: &str| openapi3::SecurityScheme::ApiKey {
+            name: "Authorization".to_string(),
+            location: "header".to_string(),
+            description: Some(description.to_string()),
+            extensions: BTreeMap::new(),
+        };
+
+        let scheme = match auth.auth_type {
+            postman::AuthType::Basic => ("basicAuth", http("basic"), vec![]),
+            postman::AuthType::Bearer => ("bearerAuth", http("bearer"), vec![]),
+            postman::AuthType::Digest => ("digestAuth", http("digest"), vec![]),
+            postman::AuthType::Apikey => {
+                let location = match attribute("in").as_deref() {
+                    Some("query") => "query",
+                    _ => "header",
+                };
+                let scheme = openapi3::SecurityScheme::ApiKey {
+                    name: attribute("key")?,
+                    location: location.to_string(),
+                    description: None,
+                    extensions: BTreeMap::new(),
+                };
+                ("apiKeyAuth", scheme, vec![])
+            }
+            postman::AuthType::Oauth2 => {
+                let url = |key: &str| attribute(key).and_then(|url| url.parse().ok());
+                let scopes: Vec<String> = attribute("scope")
+                    .map(|scope| scope.split_whitespace().map(String::from).collect())
+                    .unwrap_or_default();
+                // Postman does not describe scopes, so each flow lists them by name only.
+                let flow_scopes: BTreeMap<String, String> = scopes
+                    .iter()
+                    .map(|scope| (scope.clone(), String::new()))
+                    .collect();
+                let mut flows = openapi3::Flows::default();
+                match attribute("grant_type").as_deref() {
+                    Some("implicit") => {
+                        flows.implicit =
+                            url("authUrl").map(|authorization_url| openapi3::ImplicitFlow {
+                                authorization_url,
+                                refresh_url: None,
+                                scopes: flow_scopes.clone(),
+                                extensions: BTreeMap::new(),
+                            })
+                    }
+                    Some("password_credentials") => {
+                        flows.password =
+                            url("accessTokenUrl").map(|token_url| openapi3::PasswordFlow {
+                                token_url,
+                                refresh_url: None,
+                                scopes: flow_scopes.clone(),
+                                extensions: BTreeMap::new(),
+                            })
+                    }
+                    Some("client_credentials") => {
+                        flows.client_credentials =
+                            url("accessTokenUrl").map(|token_url| openapi3::ClientCredentialsFlow {
+                                token_url,
+                                refresh_url: None,
+                                scopes: flow_scopes.clone(),
+                                extensions: BTreeMap::new(),
+                            })
+                    }
+                    // Postman defaults to the authorization code grant, with or without PKCE.
+                    _ => {
+                        if let (Some(authorization_url), Some(token_url)) =
+                            (url("authUrl"), url("accessTokenUrl"))
+                        {
+                            flows.authorization_code = Some(openapi3::AuthorizationCodeFlow {
+                                authorization_url,
+                                token_url,
+                                refresh_url: None,
+                                scopes: flow_scopes,
+                                extensions: BTreeMap::new(),
+                            })
+                        }
+                    }
+                }
+                // A flow needs its URLs, which unresolved variables leave unusable.
+                if flows == openapi3::Flows::default() {
+                    return None;
+                }
+                let scheme = openapi3::SecurityScheme::OAuth2 {
+                    flows: Box::new(flows),
+                    description: None,
+                    extensions: BTreeMap::new(),
+                };
+                ("oauth2Auth", scheme, scopes)
+            }
+            postman::AuthType::Awsv4 => (
+                "awsv4Auth",
+                authorization_header("AWS Signature Version 4 authentication"),
+                vec![],
+            ),
+            postman::AuthType::Hawk => (
+                "hawkAuth",
+                authorization_header("Hawk authentication"),
+                vec![],
+            ),
+            postman::AuthType::Ntlm => (
+                "ntlmAuth",
+                authorization_header("NTLM authentication"),
+                vec![],
+            ),
+            postman::AuthType::Oauth1 => (
+                "oauth1Auth",
+                authorization_header("OAuth 1.0 authentication"),
+                vec![],
+            ),
+            postman::AuthType::Noauth => return None,
+        };
+        Some(scheme)
+    }
+
     fn generate_path_parameters(
         &self,
         resolved_segments: &[String],
@@ -804,12 +2128,16 @@ impl<'a> Transpiler<'a> {
                     .captures_iter(segment.as_str())
                     .map(|capture| {
                         let var = capture.get(1).unwrap().as_str();
-                        let mut param = openapi3::Parameter::default();
-                        param.name = var.to_string();
-                        param.location = "path".to_string();
-                        param.required = Some(true);
-                        let mut schema = openapi3::Schema::default();
-                        schema.schema_type = Some("string".to_string());
+                        let mut param = openapi3::Parameter {
+                            name: var.to_string(),
+                            location: "path".to_string(),
+                            required: Some(true),
+                            ..openapi3::Parameter::default()
+                        };
+                        let mut schema = openapi3::Schema {
+                            schema_type: Some("string".to_string()),
+                            ..openapi3::Schema::default()
+                        };
                         if let Some(path_val) = &postman_variables {
                             if let Some(p) = path_val.iter().find(|p| match &p.key {
                                 Some(k) => k == var,
@@ -819,9 +2147,14 @@ impl<'a> Transpiler<'a> {
 
                                 if let Some(pval) = &p.value {
                                     if let Some(pval_val) = pval.as_str() {
-                                        schema.example = Some(serde_json::Value::String(
-                                            self.resolve_variables(pval_val, VAR_REPLACE_CREDITS),
-                                        ));
+                                        schema.example =
+                                            Some(serde_json::Value::String(self.redact_field(
+                                                var,
+                                                &self.resolve_variables(
+                                                    pval_val,
+                                                    VAR_REPLACE_CREDITS,
+                                                ),
+                                            )));
                                     }
                                 }
                             }
@@ -844,34 +2177,41 @@ impl<'a> Transpiler<'a> {
         &self,
         query_params: &[postman::QueryParam],
     ) -> Option<Vec<openapi3::ObjectOrReference<openapi3::Parameter>>> {
-        let mut keys = vec![];
-        let params = query_params
-            .iter()
-            .filter_map(|qp| match qp.key {
-                Some(ref key) => {
-                    if keys.contains(&key.as_str()) {
-                        return None;
+        let params = group_query_params(query_params)
+            .into_iter()
+            .map(|(key, group)| {
+                let mut param = openapi3::Parameter {
+                    name: key.to_string(),
+                    location: "query".to_string(),
+                    description: group
+                        .iter()
+                        .find_map(|qp| extract_description(&qp.description)),
+                    ..openapi3::Parameter::default()
+                };
+                let repeated = group.len() > 1;
+                let example = self.query_param_example(key, &group, repeated);
+                let schema = if repeated {
+                    // A repeated key is a form-exploded array, e.g. `?tag=a&tag=b`.
+                    param.style = Some(openapi3::ParameterStyle::Form);
+                    param.explode = Some(true);
+                    openapi3::Schema {
+                        schema_type: Some("array".to_string()),
+                        items: Some(Box::new(openapi3::Schema {
+                            schema_type: Some("string".to_string()),
+                            ..openapi3::Schema::default()
+                        })),
+                        example,
+                        ..openapi3::Schema::default()
                     }
-
-                    keys.push(key);
-                    let mut param = openapi3::Parameter::default();
-
-                    param.name = key.to_string();
-                    param.location = "query".to_string();
-                    let mut schema = openapi3::Schema::default();
-                    schema.schema_type = Some("string".to_string());
-                    param.description = extract_description(&qp.description);
-
-                    if let Some(pval) = &qp.value {
-                        schema.example = Some(serde_json::Value::String(
-                            self.resolve_variables(pval, VAR_REPLACE_CREDITS),
-                        ));
+                } else {
+                    openapi3::Schema {
+                        schema_type: Some("string".to_string()),
+                        example,
+                        ..openapi3::Schema::default()
                     }
-
-                    param.schema = Some(schema);
-                    Some(openapi3::ObjectOrReference::Object(param))
-                }
-                None => None,
+                };
+                param.schema = Some(schema);
+                openapi3::ObjectOrReference::Object(param)
             })
             .collect::<Vec<openapi3::ObjectOrReference<openapi3::Parameter>>>();
 
@@ -881,16 +2221,724 @@ impl<'a> Transpiler<'a> {
             None
         }
     }
+
+    /// Returns the redacted value of a query parameter, as an array when the key is repeated.
+    fn query_param_example(
+        &self,
+        key: &str,
+        group: &[&postman::QueryParam],
+        repeated: bool,
+    ) -> Option<serde_json::Value> {
+        let mut values = group
+            .iter()
+            .filter_map(|qp| qp.value.as_ref())
+            .map(|value| {
+                serde_json::Value::String(
+                    self.redact_field(key, &self.resolve_variables(value, VAR_REPLACE_CREDITS)),
+                )
+            })
+            .collect::<Vec<_>>();
+        match values.len() {
+            0 => None,
+            1 if !repeated => values.pop(),
+            _ => Some(serde_json::Value::Array(values)),
+        }
+    }
+
+    /// Attaches the query parameter values of the saved responses' original requests as named
+    /// examples, when they differ from the request's own values.
+    fn add_query_param_examples(
+        &self,
+        op: &mut openapi3::Operation,
+        responses: &Option<Vec<Option<postman::ResponseClass>>>,
+    ) {
+        let params = match op.parameters.as_mut() {
+            Some(params) => params,
+            None => return,
+        };
+        for param in params.iter_mut() {
+            let param = match param {
+                openapi3::ObjectOrReference::Object(param) if param.location == "query" => param,
+                _ => continue,
+            };
+            let default = param.schema.as_ref().and_then(|s| s.example.clone());
+            let repeated = param.explode == Some(true);
+            let mut examples = BTreeMap::new();
+            for res in responses.iter().flatten().flatten() {
+                // An original request given as a plain URL string has no parsed query.
+                let url = match &res.original_request {
+                    Some(postman::RequestUnion::RequestClass(request)) => request.url.as_ref(),
+                    _ => None,
+                };
+                let query = match url {
+                    Some(postman::Url::UrlClass(url)) => match &url.query {
+                        Some(query) => query,
+                        None => continue,
+                    },
+                    _ => continue,
+                };
+                let group = query
+                    .iter()
+                    .filter(|qp| qp.key.as_deref() == Some(param.name.as_str()))
+                    .collect::<Vec<_>>();
+                if let Some(value) = self.query_param_example(&param.name, &group, repeated) {
+                    let name = res.name.clone().unwrap_or_else(|| "example".to_string());
+                    let name = unique_example_name(&examples, name);
+                    examples.insert(
+                        name,
+                        openapi3::ObjectOrReference::Object(openapi3::Example {
+                            value: Some(value),
+                            ..openapi3::Example::default()
+                        }),
+                    );
+                }
+            }
+            let redundant = examples.values().all(|example| match example {
+                openapi3::ObjectOrReference::Object(example) => example.value == default,
+                _ => false,
+            });
+            if !redundant {
+                param.examples = Some(openapi3::MediaTypeExample::Examples { examples });
+            }
+        }
+    }
+}
+
+/// Groups query parameters by key, in order of first appearance.
+fn group_query_params(
+    query_params: &[postman::QueryParam],
+) -> Vec<(&str, Vec<&postman::QueryParam>)> {
+    let mut groups: Vec<(&str, Vec<&postman::QueryParam>)> = vec![];
+    for qp in query_params {
+        let key = match &qp.key {
+            Some(key) => key.as_str(),
+            None => continue,
+        };
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(qp),
+            None => groups.push((key, vec![qp])),
+        }
+    }
+    groups
+}
+
+/// Returns the value of a Postman auth attribute as a string.
+fn auth_attribute(attributes: &postman::AuthAttributeUnion, key: &str) -> Option<String> {
+    let value = match attributes {
+        postman::AuthAttributeUnion::AuthAttribute21(attributes) => attributes
+            .iter()
+            .find(|attribute| attribute.key == key)?
+            .value
+            .clone()?,
+        // The untagged union also matches v2.1 attribute lists, so both shapes are handled here.
+        postman::AuthAttributeUnion::AuthAttribute20(attributes) => match attributes.as_ref()? {
+            serde_json::Value::Object(attributes) => attributes.get(key)?.clone(),
+            serde_json::Value::Array(attributes) => attributes
+                .iter()
+                .find(|attribute| attribute.get("key").and_then(|k| k.as_str()) == Some(key))?
+                .get("value")?
+                .clone(),
+            _ => return None,
+        },
+    };
+    match value {
+        serde_json::Value::String(value) => Some(value),
+        serde_json::Value::Null => None,
+        value => Some(value.to_string()),
+    }
+}
+
+fn discriminator_value(schema: &openapi3::Schema, property_name: &str) -> Option<String> {
+    if schema.schema_type.as_deref() != Some("object") {
+        return None;
+    }
+    let property = schema.properties.as_ref()?.get(property_name)?;
+    if property.schema_type.as_deref() != Some("string") {
+        return None;
+    }
+    match (&property.enum_values, &property.example) {
+        (Some(values), _) if values.len() == 1 => values[0].as_str().map(String::from),
+        (_, Some(serde_json::Value::String(value))) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn find_discriminator(original: &openapi3::Schema, new: &openapi3::Schema) -> Option<String> {
+    let original_keys = original
+        .properties
+        .as_ref()?
+        .keys()
+        .collect::<std::collections::BTreeSet<_>>();
+    let new_keys = new
+        .properties
+        .as_ref()?
+        .keys()
+        .collect::<std::collections::BTreeSet<_>>();
+    if original_keys == new_keys {
+        return None;
+    }
+
+    DISCRIMINATOR_PROPERTIES
+        .iter()
+        .find(|name| {
+            match (
+                discriminator_value(original, name),
+                discriminator_value(new, name),
+            ) {
+                (Some(a), Some(b)) => a != b,
+                _ => false,
+            }
+        })
+        .map(|name| name.to_string())
+}
+
+fn discriminated_variant(mut schema: openapi3::Schema, property_name: &str) -> openapi3::Schema {
+    if let Some(value) = discriminator_value(&schema, property_name) {
+        if let Some(properties) = &mut schema.properties {
+            if let Some(property) = properties.get_mut(property_name) {
+                property.enum_values = Some(vec![serde_json::Value::String(value)]);
+            }
+        }
+        let required = schema.required.get_or_insert_with(Vec::new);
+        if !required.iter().any(|r| r == property_name) {
+            required.push(property_name.to_string());
+        }
+    }
+    schema
+}
+
+/// Returns the lowercased media type of a Content-Type value without its parameters.
+fn media_type_essence(content_type: &str) -> Option<String> {
+    let essence = content_type.split(';').next()?.trim().to_lowercase();
+    if essence.is_empty() {
+        None
+    } else {
+        Some(essence)
+    }
+}
+
+/// Returns the reason phrase registered for a status code, or the name of its class.
+fn reason_phrase(code: i64) -> &'static str {
+    registered_reason_phrase(code).unwrap_or(match code / 100 {
+        1 => "Informational",
+        2 => "Success",
+        3 => "Redirection",
+        4 => "Client Error",
+        5 => "Server Error",
+        _ => "Response",
+    })
+}
+
+/// Returns the reason phrase registered for a status code.
+fn registered_reason_phrase(code: i64) -> Option<&'static str> {
+    let phrase = match code {
+        100 => "Continue",
+        101 => "Switching Protocols",
+        102 => "Processing",
+        103 => "Early Hints",
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        203 => "Non-Authoritative Information",
+        204 => "No Content",
+        205 => "Reset Content",
+        206 => "Partial Content",
+        207 => "Multi-Status",
+        208 => "Already Reported",
+        226 => "IM Used",
+        300 => "Multiple Choices",
+        301 => "Moved Permanently",
+        302 => "Found",
+        303 => "See Other",
+        304 => "Not Modified",
+        305 => "Use Proxy",
+        307 => "Temporary Redirect",
+        308 => "Permanent Redirect",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        402 => "Payment Required",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        407 => "Proxy Authentication Required",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        410 => "Gone",
+        411 => "Length Required",
+        412 => "Precondition Failed",
+        413 => "Payload Too Large",
+        414 => "URI Too Long",
+        415 => "Unsupported Media Type",
+        416 => "Range Not Satisfiable",
+        417 => "Expectation Failed",
+        421 => "Misdirected Request",
+        422 => "Unprocessable Entity",
+        423 => "Locked",
+        424 => "Failed Dependency",
+        425 => "Too Early",
+        426 => "Upgrade Required",
+        428 => "Precondition Required",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        451 => "Unavailable For Legal Reasons",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        505 => "HTTP Version Not Supported",
+        506 => "Variant Also Negotiates",
+        507 => "Insufficient Storage",
+        508 => "Loop Detected",
+        510 => "Not Extended",
+        511 => "Network Authentication Required",
+        _ => return None,
+    };
+    Some(phrase)
+}
+
+fn named_examples(
+    examples: openapi3::MediaTypeExample,
+) -> BTreeMap<String, openapi3::ObjectOrReference<openapi3::Example>> {
+    match examples {
+        openapi3::MediaTypeExample::Examples { examples } => examples,
+        openapi3::MediaTypeExample::Example { example } => {
+            let mut examples = BTreeMap::new();
+            examples.insert(
+                "example".to_string(),
+                openapi3::ObjectOrReference::Object(openapi3::Example {
+                    value: Some(example),
+                    ..openapi3::Example::default()
+                }),
+            );
+            examples
+        }
+    }
+}
+
+/// Returns the example name, suffixed with a counter when it is already taken.
+fn unique_example_name<T>(examples: &BTreeMap<String, T>, name: String) -> String {
+    if !examples.contains_key(&name) {
+        return name;
+    }
+    let base = if name.is_empty() {
+        "example".to_string()
+    } else {
+        name
+    };
+    (1..)
+        .map(|i| format!("{} {}", base, i + 1))
+        .find(|candidate| !examples.contains_key(candidate))
+        .unwrap_or(base)
+}
+
+fn is_json_media_type(media_type: &str) -> bool {
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+fn is_xml_media_type(media_type: &str) -> bool {
+    media_type == "application/xml" || media_type == "text/xml" || media_type.ends_with("+xml")
+}
+
+/// Guesses the media type of a body that is not JSON.
+fn sniff_media_type(body: &str) -> &'static str {
+    let trimmed = body.trim_start();
+    let lowercased = trimmed.chars().take(512).collect::<String>().to_lowercase();
+
+    if trimmed.starts_with('<') {
+        if lowercased.starts_with("<!doctype html")
+            || lowercased.starts_with("<html")
+            || lowercased.contains("<body")
+            || lowercased.contains("<head")
+        {
+            return "text/html";
+        }
+        if roxmltree::Document::parse(trimmed).is_ok() {
+            return "application/xml";
+        }
+        return "text/plain";
+    }
+
+    if JAVASCRIPT_RE.is_match(trimmed) {
+        return "application/javascript";
+    }
+
+    let lines = trimmed
+        .lines()
+        .map(|l| l.trim_end())
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>();
+
+    if lines.len() > 1 {
+        let columns = lines[0].matches(',').count();
+        if columns > 0 && lines.iter().all(|l| l.matches(',').count() == columns) {
+            return "text/csv";
+        }
+    }
+
+    if trimmed.starts_with("---")
+        || (lines.len() > 1
+            && lines
+                .iter()
+                .all(|l| l.trim_start().starts_with('#') || YAML_LINE_RE.is_match(l)))
+    {
+        return "application/yaml";
+    }
+
+    "text/plain"
+}
+
+/// Guesses the media type of a file from the extension of its path.
+fn file_media_type(path: &str) -> Option<&'static str> {
+    let name = path.rsplit(|c| c == '/' || c == '\\').next()?;
+    let extension = name.rsplit('.').next().filter(|e| e.len() < name.len())?;
+    FILE_MEDIA_TYPES
+        .iter()
+        .find(|(e, _)| e.eq_ignore_ascii_case(extension))
+        .map(|(_, media_type)| *media_type)
+}
+
+fn xml_metadata(node: roxmltree::Node) -> Option<openapi3::Xml> {
+    let namespace = node.tag_name().namespace()?;
+    Some(openapi3::Xml {
+        namespace: Some(namespace.to_string()),
+        prefix: node.lookup_prefix(namespace).map(|p| p.to_string()),
+        ..openapi3::Xml::default()
+    })
+}
+
+fn clear_schema_examples(schema: &mut openapi3::Schema) {
+    schema.example = None;
+    if let Some(items) = &mut schema.items {
+        clear_schema_examples(items);
+    }
+    if let Some(properties) = &mut schema.properties {
+        for property in properties.values_mut() {
+            clear_schema_examples(property);
+        }
+    }
+    for schemas in vec![&mut schema.all_of, &mut schema.one_of, &mut schema.any_of]
+        .into_iter()
+        .flatten()
+    {
+        for s in schemas.iter_mut() {
+            if let openapi3::ObjectOrReference::Object(s) = s {
+                clear_schema_examples(s);
+            }
+        }
+    }
+}
+
+/// Converts Postman events into entries of the `x-postman-events` extension. Events inherited
+/// from a folder name it.
+fn postman_events(
+    events: &Option<Vec<postman::Event>>,
+    folder: Option<&str>,
+) -> Vec<serde_json::Value> {
+    events
+        .iter()
+        .flatten()
+        .map(|event| {
+            let mut entry = serde_json::Map::new();
+            entry.insert(
+                "listen".to_string(),
+                serde_json::Value::String(event.listen.clone()),
+            );
+            if let Some(folder) = folder {
+                entry.insert(
+                    "folder".to_string(),
+                    serde_json::Value::String(folder.to_string()),
+                );
+            }
+            if event.disabled == Some(true) {
+                entry.insert("disabled".to_string(), serde_json::Value::Bool(true));
+            }
+            if let Some(script) = &event.script {
+                let exec = match &script.exec {
+                    Some(postman::Host::String(source)) => {
+                        source.lines().map(String::from).collect()
+                    }
+                    Some(postman::Host::StringArray(lines)) => lines.clone(),
+                    None => vec![],
+                };
+                entry.insert("exec".to_string(), serde_json::json!(exec));
+                let src = match &script.src {
+                    Some(postman::Url::String(src)) => Some(src.clone()),
+                    Some(postman::Url::UrlClass(src)) => src.raw.clone(),
+                    None => None,
+                };
+                if let Some(src) = src {
+                    entry.insert("src".to_string(), serde_json::Value::String(src));
+                }
+            }
+            serde_json::Value::Object(entry)
+        })
+        .collect()
+}
+
+/// Declares webhooks as callbacks of the operations that accept a callback URL, or as reusable
+/// callbacks in `components.callbacks` when no operation does.
+fn add_callbacks(oas: &mut openapi3::Spec, webhooks: Vec<Webhook>) {
+    if webhooks.is_empty() {
+        return;
+    }
+
+    let mut subscribed = false;
+    for path in oas.paths.values_mut() {
+        for op in operations_mut(path) {
+            let expression = match callback_url_expression(op) {
+                Some(expression) => expression,
+                None => continue,
+            };
+            subscribed = true;
+            let callbacks = op.callbacks.get_or_insert_with(BTreeMap::new);
+            for webhook in &webhooks {
+                let mut paths = BTreeMap::new();
+                paths.insert(expression.clone(), webhook.item.clone());
+                callbacks.insert(
+                    webhook.name.clone(),
+                    openapi3::ObjectOrReference::Object(openapi3::Callback {
+                        paths,
+                        extensions: BTreeMap::new(),
+                    }),
+                );
+            }
+        }
+    }
+
+    if !subscribed {
+        let callbacks = oas
+            .components
+            .get_or_insert_with(Default::default)
+            .callbacks
+            .get_or_insert_with(BTreeMap::new);
+        for webhook in webhooks {
+            let mut paths = BTreeMap::new();
+            paths.insert(webhook.path, webhook.item);
+            callbacks.insert(
+                webhook.name,
+                openapi3::ObjectOrReference::Object(openapi3::Callback {
+                    paths,
+                    extensions: BTreeMap::new(),
+                }),
+            );
+        }
+    }
+}
+
+/// Declares a `mutualTLS` security scheme for each client certificate and requires it, in
+/// addition to any other security, on the operations whose requests present it.
+fn add_mutual_tls(oas: &mut openapi::v3_1::Spec, certificates: Vec<(String, Option<String>)>) {
+    if certificates.is_empty() {
+        return;
+    }
+
+    let mut scheme_names = BTreeMap::<Option<String>, String>::new();
+    for (_, certificate) in &certificates {
+        if !scheme_names.contains_key(certificate) {
+            let scheme_name = match scheme_names.len() {
+                0 => "mutualTLS".to_string(),
+                n => format!("mutualTLS{}", n + 1),
+            };
+            scheme_names.insert(certificate.clone(), scheme_name);
+        }
+    }
+    let schemes = oas
+        .components
+        .get_or_insert_with(Default::default)
+        .security_schemes
+        .get_or_insert_with(BTreeMap::new);
+    for (certificate, scheme_name) in &scheme_names {
+        schemes.insert(
+            scheme_name.clone(),
+            openapi::v3_1::ObjectOrReference::Object(openapi::v3_1::SecurityScheme::MutualTls {
+                description: certificate
+                    .as_ref()
+                    .map(|name| format!("The client certificate {}.", name)),
+                extensions: BTreeMap::new(),
+            }),
+        );
+    }
+
+    let top_level = oas.security.clone();
+    for path in oas.paths.iter_mut().flat_map(|paths| paths.values_mut()) {
+        let operations = vec![
+            &mut path.get,
+            &mut path.put,
+            &mut path.post,
+            &mut path.delete,
+            &mut path.options,
+            &mut path.head,
+            &mut path.patch,
+            &mut path.trace,
+        ];
+        for op in operations.into_iter().flatten() {
+            let certificate = certificates
+                .iter()
+                .find(|(operation_id, _)| Some(operation_id) == op.operation_id.as_ref());
+            let scheme_name = match certificate {
+                Some((_, certificate)) => &scheme_names[certificate],
+                None => continue,
+            };
+            let mut security = op
+                .security
+                .clone()
+                .or_else(|| top_level.clone())
+                .unwrap_or_default();
+            if security.is_empty() {
+                security.push(openapi3::SecurityRequirement::new());
+            }
+            for requirement in &mut security {
+                requirement.insert(scheme_name.clone(), vec![]);
+            }
+            op.security = Some(security);
+        }
+    }
+}
+
+/// Returns a runtime expression for the callback URL accepted by an operation, e.g.
+/// `{$request.body#/callbackUrl}`.
+fn callback_url_expression(op: &openapi3::Operation) -> Option<String> {
+    for param in op.parameters.iter().flatten() {
+        if let openapi3::ObjectOrReference::Object(param) = param {
+            if param.location == "query" && CALLBACK_URL_RE.is_match(&param.name) {
+                return Some(format!("{{$request.query.{}}}", param.name));
+            }
+        }
+    }
+    let body = match &op.request_body {
+        Some(openapi3::ObjectOrReference::Object(body)) => body,
+        _ => return None,
+    };
+    body.content
+        .values()
+        .filter_map(|media_type| match &media_type.schema {
+            Some(openapi3::ObjectOrReference::Object(schema)) => schema.properties.as_ref(),
+            _ => None,
+        })
+        .flat_map(|properties| properties.keys())
+        .find(|name| CALLBACK_URL_RE.is_match(name))
+        .map(|name| format!("{{$request.body#/{}}}", name))
+}
+
+fn operations_mut(path: &mut openapi3::PathItem) -> Vec<&mut openapi3::Operation> {
+    vec![
+        &mut path.get,
+        &mut path.put,
+        &mut path.post,
+        &mut path.delete,
+        &mut path.options,
+        &mut path.head,
+        &mut path.patch,
+        &mut path.trace,
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Moves media type examples whose JSON exceeds `threshold` bytes into `components.examples`,
+/// leaving a reference in their place.
+fn reference_large_examples(oas: &mut openapi3::Spec, threshold: usize) {
+    let mut examples = BTreeMap::<String, openapi3::ObjectOrReference<openapi3::Example>>::new();
+
+    for path in oas.paths.values_mut() {
+        for op in operations_mut(path) {
+            let op_id = op.operation_id.clone().unwrap_or_default();
+            if let Some(openapi3::ObjectOrReference::Object(body)) = &mut op.request_body {
+                for content in body.content.values_mut() {
+                    let name = format!("{}Request", op_id);
+                    reference_media_type_examples(content, &name, threshold, &mut examples);
+                }
+            }
+            for (code, response) in op.responses.iter_mut() {
+                if let Some(content) = &mut response.content {
+                    for media_type in content.values_mut() {
+                        let name = format!("{}{}", op_id, code.to_case(Case::Pascal));
+                        reference_media_type_examples(media_type, &name, threshold, &mut examples);
+                    }
+                }
+            }
+        }
+    }
+
+    if !examples.is_empty() {
+        let components = oas.components.get_or_insert_with(Default::default);
+        components
+            .examples
+            .get_or_insert_with(BTreeMap::new)
+            .append(&mut examples);
+    }
+}
+
+fn reference_media_type_examples(
+    media_type: &mut openapi3::MediaType,
+    name: &str,
+    threshold: usize,
+    examples: &mut BTreeMap<String, openapi3::ObjectOrReference<openapi3::Example>>,
+) {
+    let exceeds = |value: &serde_json::Value| {
+        serde_json::to_string(value)
+            .map(|s| s.len() > threshold)
+            .unwrap_or(false)
+    };
+    let mut reference = |example: openapi3::Example| {
+        let mut component_name = name.to_string();
+        let mut i: usize = 0;
+        while examples.contains_key(&component_name) {
+            i += 1;
+            component_name = format!("{}{}", name, i);
+        }
+        let ref_path = format!("#/components/examples/{}", component_name);
+        examples.insert(component_name, openapi3::ObjectOrReference::Object(example));
+        openapi3::ObjectOrReference::Ref { ref_path }
+    };
+
+    match media_type.examples.take() {
+        Some(openapi3::MediaTypeExample::Example { example }) if exceeds(&example) => {
+            let mut example_map =
+                BTreeMap::<String, openapi3::ObjectOrReference<openapi3::Example>>::new();
+            example_map.insert(
+                "default".to_string(),
+                reference(openapi3::Example {
+                    value: Some(example),
+                    ..openapi3::Example::default()
+                }),
+            );
+            media_type.examples = Some(openapi3::MediaTypeExample::Examples {
+                examples: example_map,
+            });
+        }
+        Some(openapi3::MediaTypeExample::Examples {
+            examples: example_map,
+        }) => {
+            let example_map = example_map
+                .into_iter()
+                .map(|(key, example)| match example {
+                    openapi3::ObjectOrReference::Object(ex)
+                        if ex.value.as_ref().map(&exceeds).unwrap_or(false) =>
+                    {
+                        (key, reference(ex))
+                    }
+                    _ => (key, example),
+                })
+                .collect();
+            media_type.examples = Some(openapi3::MediaTypeExample::Examples {
+                examples: example_map,
+            });
+        }
+        examples => media_type.examples = examples,
+    }
 }
 
 fn extract_description(description: &Option<postman::DescriptionUnion>) -> Option<String> {
     match description {
         Some(d) => match d {
             postman::DescriptionUnion::String(s) => Some(s.to_string()),
-            postman::DescriptionUnion::Description(desc) => match &desc.content {
-                Some(c) => Some(c.to_string()),
-                None => None,
-            },
+            postman::DescriptionUnion::Description(desc) => {
+                desc.content.as_ref().map(|c| c.to_string())
+            }
         },
         None => None,
     }
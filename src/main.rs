@@ -1,6 +1,9 @@
 use clap::{crate_authors, crate_version, App, AppSettings, Arg};
 use lazy_static::lazy_static;
-use postman2openapi::{from_path, from_str, TranspileOptions};
+use postman2openapi::{
+    from_str_with_warnings, openapi2postman, ExampleOptions, RedactionOptions, ResponseDescription,
+    SpecVersion, TranspileOptions,
+};
 use std::io::{stdin, Read};
 
 fn main() {
@@ -28,6 +31,84 @@ fn main() {
                 .possible_values(&["yaml", "json"])
                 .default_value("yaml"),
         )
+        .arg(
+            Arg::new("max-example-items")
+                .long("max-example-items")
+                .about("Truncate arrays in examples to at most this many items")
+                .value_name("count")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("max-example-length")
+                .long("max-example-length")
+                .about("Truncate strings in examples to at most this many characters")
+                .value_name("count")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("omit-schema-examples")
+                .long("omit-schema-examples")
+                .about("Omit schema property examples when the media type has an example"),
+        )
+        .arg(
+            Arg::new("example-ref-threshold")
+                .long("example-ref-threshold")
+                .about("Move examples larger than this many bytes into components.examples")
+                .value_name("bytes")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("redact")
+                .long("redact")
+                .about("Redact credentials, tokens, passwords and secrets from examples"),
+        )
+        .arg(
+            Arg::new("redact-key")
+                .long("redact-key")
+                .about("Redact the values of fields with this name (may be repeated)")
+                .value_name("name")
+                .takes_value(true)
+                .multiple_occurrences(true),
+        )
+        .arg(
+            Arg::new("redact-pattern")
+                .long("redact-pattern")
+                .about("Redact text matching this regular expression (may be repeated)")
+                .value_name("regex")
+                .takes_value(true)
+                .multiple_occurrences(true),
+        )
+        .arg(
+            Arg::new("response-description")
+                .long("response-description")
+                .about("Where response descriptions come from")
+                .value_name("source")
+                .possible_values(&["name", "status", "reason"])
+                .default_value("name"),
+        )
+        .arg(
+            Arg::new("spec-version")
+                .long("spec-version")
+                .about("The version of the OpenAPI specification to emit")
+                .value_name("version")
+                .possible_values(&["3.0", "3.1", "2.0"])
+                .default_value("3.0"),
+        )
+        .arg(
+            Arg::new("source-annotations")
+                .long("source-annotations")
+                .about("Annotate the output with the Postman IDs, names, folders and URLs"),
+        )
+        .arg(
+            Arg::new("lenient")
+                .long("lenient")
+                .about("Skip the collection items that fail validation instead of failing"),
+        )
+        .arg(
+            Arg::new("to-postman")
+                .long("to-postman")
+                .about("Convert an OpenAPI document into a Postman collection instead"),
+        )
         .arg(
             Arg::new("INPUT")
                 .value_name("input-file")
@@ -44,19 +125,91 @@ fn main() {
 
     let mut buffer = String::new();
     let format = matches.value_of_t("output").unwrap_or_else(|e| e.exit());
-    match &matches.value_of("INPUT") {
-        Some(filename) => match from_path(filename, TranspileOptions { format }) {
-            Ok(oas) => println!("{}", oas),
-            Err(err) => eprintln!("{}", err),
+    let examples = ExampleOptions {
+        max_array_length: optional_value(&matches, "max-example-items"),
+        max_string_length: optional_value(&matches, "max-example-length"),
+        omit_schema_examples: matches.is_present("omit-schema-examples"),
+        reference_threshold: optional_value(&matches, "example-ref-threshold"),
+    };
+    let redaction = RedactionOptions {
+        default_rules: matches.is_present("redact"),
+        keys: matches
+            .values_of("redact-key")
+            .map(|keys| keys.map(String::from).collect())
+            .unwrap_or_default(),
+        patterns: matches
+            .values_of("redact-pattern")
+            .map(|patterns| {
+                patterns
+                    .map(|p| match regex::Regex::new(p) {
+                        Ok(re) => re,
+                        Err(err) => {
+                            eprintln!("postman2openapi: invalid redaction pattern: {}", err);
+                            std::process::exit(2);
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    let response_description: ResponseDescription = matches
+        .value_of_t("response-description")
+        .unwrap_or_else(|e| e.exit());
+    let spec_version: SpecVersion = matches
+        .value_of_t("spec-version")
+        .unwrap_or_else(|e| e.exit());
+    let options = TranspileOptions::default()
+        .with_format(format)
+        .with_examples(examples)
+        .with_redaction(redaction)
+        .with_response_description(response_description)
+        .with_spec_version(spec_version)
+        .with_source_annotations(matches.is_present("source-annotations"))
+        .with_lenient(matches.is_present("lenient"));
+    let collection = match &matches.value_of("INPUT") {
+        Some(filename) => match std::fs::read_to_string(filename) {
+            Ok(collection) => collection,
+            Err(err) => {
+                eprintln!("{}", err);
+                return;
+            }
         },
         None => match stdin().read_to_string(&mut buffer) {
-            Ok(_) => match from_str(&buffer, TranspileOptions { format }) {
-                Ok(oas) => println!("{}", oas),
-                Err(err) => eprintln!("{}", err),
-            },
-            Err(_) => eprintln!("postman2openapi: warning: recursive search of stdin"),
+            Ok(_) => buffer,
+            Err(_) => {
+                eprintln!("postman2openapi: warning: recursive search of stdin");
+                return;
+            }
         },
     };
+    if matches.is_present("to-postman") {
+        match openapi2postman::from_str(&collection) {
+            Ok(collection) => println!("{}", collection),
+            Err(err) => eprintln!("{}", err),
+        }
+        return;
+    }
+    match from_str_with_warnings(&collection, options) {
+        Ok((oas, warnings)) => {
+            for warning in warnings {
+                eprintln!("postman2openapi: warning: {}", warning);
+            }
+            println!("{}", oas);
+        }
+        Err(err) => eprintln!("{}", err),
+    };
+}
+
+fn optional_value<T>(matches: &clap::ArgMatches, name: &str) -> Option<T>
+where
+    T: std::str::FromStr,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+{
+    if matches.is_present(name) {
+        Some(matches.value_of_t(name).unwrap_or_else(|e| e.exit()))
+    } else {
+        None
+    }
 }
 
 pub fn long_version() -> String {
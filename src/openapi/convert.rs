@@ -0,0 +1,2015 @@
+//! Conversion between versions of the OpenApi specification.
+//!
+//! Constructs that have no equivalent in the target version are dropped, and each one is
+//! reported as a warning prefixed with a JSON pointer to it in the source specification.
+
+use super::{v2, v3_0, v3_1, Error, OpenApi, Result};
+use std::collections::BTreeMap;
+use url::Url;
+
+/// A specification converted to another version of the OpenApi specification.
+#[derive(Clone, Debug)]
+pub struct Conversion<T> {
+    pub spec: T,
+    /// The constructs of the source specification that could not be represented.
+    pub warnings: Vec<String>,
+}
+
+impl OpenApi {
+    /// Converts the specification to Swagger 2.0. OpenAPI 3.1 documents are not supported.
+    pub fn to_v2(&self) -> Result<Conversion<v2::Spec>> {
+        match self {
+            OpenApi::V2(spec) => Ok(Conversion {
+                spec: spec.clone(),
+                warnings: vec![],
+            }),
+            OpenApi::V3_0(spec) => {
+                let mut downgrade = Downgrade {
+                    spec,
+                    dropped_schemes: vec![],
+                    warnings: vec![],
+                };
+                let spec = downgrade.spec();
+                Ok(Conversion {
+                    spec,
+                    warnings: downgrade.warnings,
+                })
+            }
+            OpenApi::V3_1(_) => Err(Error::UnsupportedConversion("OpenAPI 3.1", "Swagger 2.0")),
+        }
+    }
+
+    /// Converts the specification to OpenAPI 3.0. OpenAPI 3.1 documents are not supported.
+    pub fn to_v3(&self) -> Result<Conversion<v3_0::Spec>> {
+        match self {
+            OpenApi::V2(spec) => {
+                let mut upgrade = Upgrade {
+                    spec,
+                    warnings: vec![],
+                };
+                let spec = upgrade.spec();
+                Ok(Conversion {
+                    spec,
+                    warnings: upgrade.warnings,
+                })
+            }
+            OpenApi::V3_0(spec) => Ok(Conversion {
+                spec: (**spec).clone(),
+                warnings: vec![],
+            }),
+            OpenApi::V3_1(_) => Err(Error::UnsupportedConversion("OpenAPI 3.1", "OpenAPI 3.0")),
+        }
+    }
+}
+
+impl v3_0::Spec {
+    /// Converts the specification to OpenAPI 3.1, which can express everything in 3.0.
+    pub fn to_v3_1(&self) -> Result<v3_1::Spec> {
+        let mut value = serde_json::to_value(self)?;
+        upgrade_schemas(&mut value);
+        value["openapi"] = serde_json::Value::String("3.1.0".to_string());
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// Upgrades every schema in a serialized OpenAPI 3.0 document.
+fn upgrade_schemas(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, child) in object.iter_mut() {
+                match key.as_str() {
+                    "schema" => upgrade_schema(child),
+                    "schemas" => {
+                        if let serde_json::Value::Object(schemas) = child {
+                            schemas.values_mut().for_each(upgrade_schema);
+                        }
+                    }
+                    // Examples and extensions are data, whatever keys they contain.
+                    "example" | "examples" => {}
+                    key if key.starts_with("x-") => {}
+                    _ => upgrade_schemas(child),
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(upgrade_schemas),
+        _ => {}
+    }
+}
+
+/// Rewrites the keywords of an OpenAPI 3.0 schema whose meaning changed in JSON Schema 2020-12.
+fn upgrade_schema(value: &mut serde_json::Value) {
+    let object = match value.as_object_mut() {
+        Some(object) => object,
+        None => return,
+    };
+
+    if let Some(example) = object.remove("example") {
+        object.insert(
+            "examples".to_string(),
+            serde_json::Value::Array(vec![example]),
+        );
+    }
+    if object.remove("nullable") == Some(serde_json::Value::Bool(true)) {
+        match object.get("type").cloned() {
+            Some(serde_json::Value::String(schema_type)) => {
+                object.insert(
+                    "type".to_string(),
+                    serde_json::Value::Array(vec![
+                        serde_json::Value::String(schema_type),
+                        serde_json::Value::String("null".to_string()),
+                    ]),
+                );
+            }
+            Some(_) => {}
+            // Alternatives gain a null one, and a schema constraining nothing else is null only.
+            None => {
+                let alternatives = ["oneOf", "anyOf"]
+                    .iter()
+                    .find(|keyword| object.contains_key(**keyword));
+                match alternatives.and_then(|keyword| object.get_mut(*keyword)) {
+                    Some(serde_json::Value::Array(schemas)) => {
+                        schemas.push(serde_json::json!({"type": "null"}));
+                    }
+                    _ => {
+                        if !object.contains_key("allOf") && !object.contains_key("$ref") {
+                            object.insert(
+                                "type".to_string(),
+                                serde_json::Value::String("null".to_string()),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(serde_json::Value::Array(values)) = object.get_mut("enum") {
+            if !values.contains(&serde_json::Value::Null) {
+                values.push(serde_json::Value::Null);
+            }
+        }
+    }
+    for (exclusive, limit) in &[
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        if object.remove(*exclusive) == Some(serde_json::Value::Bool(true)) {
+            if let Some(limit) = object.remove(*limit) {
+                object.insert(exclusive.to_string(), limit);
+            }
+        }
+    }
+
+    for keyword in &["items", "not", "additionalProperties"] {
+        if let Some(schema) = object.get_mut(*keyword) {
+            upgrade_schema(schema);
+        }
+    }
+    for keyword in &["allOf", "oneOf", "anyOf"] {
+        if let Some(serde_json::Value::Array(schemas)) = object.get_mut(*keyword) {
+            schemas.iter_mut().for_each(upgrade_schema);
+        }
+    }
+    if let Some(serde_json::Value::Object(properties)) = object.get_mut("properties") {
+        properties.values_mut().for_each(upgrade_schema);
+    }
+}
+
+/// Converts an OpenAPI 3.0 specification to Swagger 2.0.
+struct Downgrade<'a> {
+    spec: &'a v3_0::Spec,
+    /// Security schemes that could not be converted, and so are removed from requirements.
+    dropped_schemes: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl<'a> Downgrade<'a> {
+    fn warn(&mut self, pointer: &str, message: &str) {
+        self.warnings.push(format!("{}: {}", pointer, message));
+    }
+
+    fn spec(&mut self) -> v2::Spec {
+        let spec = self.spec;
+        let mut v2_spec = v2::Spec {
+            swagger: "2.0".to_string(),
+            info: self.info(&spec.info),
+            ..v2::Spec::default()
+        };
+
+        if let Some(servers) = &spec.servers {
+            self.servers(servers, &mut v2_spec);
+        }
+        if let Some(components) = &spec.components {
+            self.components(components, &mut v2_spec);
+        }
+
+        v2_spec.tags = spec.tags.as_ref().map(|tags| {
+            tags.iter()
+                .enumerate()
+                .map(|(i, tag)| {
+                    if !tag.extensions.is_empty() {
+                        self.warn(
+                            &format!("#/tags/{}", i),
+                            "specification extensions are not supported",
+                        );
+                    }
+                    v2::Tag {
+                        name: tag.name.clone(),
+                        description: tag.description.clone(),
+                        external_docs: None,
+                    }
+                })
+                .collect()
+        });
+
+        for (path, item) in &spec.paths {
+            let pointer = format!("#/paths/{}", pointer_token(path));
+            let item = self.path_item(&pointer, item);
+            v2_spec.paths.insert(path.clone(), item);
+        }
+
+        v2_spec.security = spec
+            .security
+            .as_ref()
+            .map(|security| self.security_requirements(security));
+        if spec.external_docs.is_some() {
+            self.warn("#/externalDocs", "external docs are not supported");
+        }
+        v2_spec.extensions = spec.extensions.clone();
+
+        v2_spec
+    }
+
+    fn info(&mut self, info: &v3_0::Info) -> v2::Info {
+        v2::Info {
+            title: Some(info.title.clone()),
+            description: info.description.clone(),
+            terms_of_service: info.terms_of_service.as_ref().map(|url| url.to_string()),
+            contact: info.contact.as_ref().map(|contact| v2::Contact {
+                name: contact.name.clone(),
+                url: contact.url.as_ref().map(|url| url.to_string()),
+                email: contact.email.clone(),
+            }),
+            license: info.license.as_ref().map(|license| v2::License {
+                name: Some(license.name.clone()),
+                url: license.url.as_ref().map(|url| url.to_string()),
+            }),
+            version: Some(info.version.clone()),
+            extensions: info.extensions.clone(),
+        }
+    }
+
+    /// Maps the servers to `host`, `basePath` and `schemes`. Swagger 2.0 has a single host and
+    /// base path, so only the servers that agree with the first one are kept.
+    fn servers(&mut self, servers: &[v3_0::Server], spec: &mut v2::Spec) {
+        let mut schemes = vec![];
+        for (i, server) in servers.iter().enumerate() {
+            let pointer = format!("#/servers/{}", i);
+            let mut url = server.url.clone();
+            if let Some(variables) = &server.variables {
+                for (name, variable) in variables {
+                    url = url.replace(&format!("{{{}}}", name), &variable.default);
+                }
+                if !variables.is_empty() {
+                    self.warn(
+                        &pointer,
+                        "server variables are replaced with their default values",
+                    );
+                }
+            }
+
+            let (scheme, rest) = match url.find("://") {
+                Some(end) => (Some(url[..end].to_lowercase()), &url[end + 3..]),
+                None => (None, url.as_str()),
+            };
+            let (host, path) = if scheme.is_some() || rest.starts_with("//") {
+                let rest = rest.trim_start_matches('/');
+                match rest.find('/') {
+                    Some(end) => (Some(rest[..end].to_string()), &rest[end..]),
+                    None => (Some(rest.to_string()), ""),
+                }
+            } else {
+                (None, rest)
+            };
+            let path = path.trim_end_matches('/');
+            let base_path = if path.is_empty() {
+                None
+            } else {
+                Some(path.to_string())
+            };
+
+            if i == 0 {
+                spec.host = host;
+                spec.base_path = base_path;
+            } else if host != spec.host || base_path != spec.base_path {
+                self.warn(
+                    &pointer,
+                    "only servers with the same host and base path as the first are supported",
+                );
+                continue;
+            }
+            let scheme = scheme.and_then(|scheme| {
+                serde_json::from_value::<v2::Scheme>(serde_json::Value::String(scheme)).ok()
+            });
+            if let Some(scheme) = scheme {
+                if !schemes.contains(&scheme) {
+                    schemes.push(scheme);
+                }
+            }
+        }
+        if !schemes.is_empty() {
+            spec.schemes = Some(schemes);
+        }
+    }
+
+    fn components(&mut self, components: &v3_0::Components, spec: &mut v2::Spec) {
+        // Schemes are converted first so that requirements on dropped schemes can be removed.
+        if let Some(schemes) = &components.security_schemes {
+            let mut definitions = BTreeMap::new();
+            for (name, scheme) in schemes {
+                let pointer = format!("#/components/securitySchemes/{}", pointer_token(name));
+                let security = match scheme {
+                    v3_0::ObjectOrReference::Object(scheme) => {
+                        self.security_scheme(&pointer, scheme)
+                    }
+                    v3_0::ObjectOrReference::Ref { .. } => {
+                        self.warn(&pointer, "security scheme references are not supported");
+                        None
+                    }
+                };
+                match security {
+                    Some(security) => {
+                        definitions.insert(name.clone(), security);
+                    }
+                    None => self.dropped_schemes.push(name.clone()),
+                }
+            }
+            if !definitions.is_empty() {
+                spec.security_definitions = Some(definitions);
+            }
+        }
+
+        if let Some(schemas) = &components.schemas {
+            let definitions = schemas
+                .iter()
+                .map(|(name, schema)| {
+                    let pointer = format!("#/components/schemas/{}", pointer_token(name));
+                    (name.clone(), self.schema_or_reference(&pointer, schema))
+                })
+                .collect();
+            spec.definitions = Some(definitions);
+        }
+
+        if let Some(parameters) = &components.parameters {
+            let mut v2_parameters = BTreeMap::new();
+            for (name, parameter) in parameters {
+                let pointer = format!("#/components/parameters/{}", pointer_token(name));
+                match parameter {
+                    v3_0::ObjectOrReference::Object(parameter) => {
+                        if let Some(parameter) = self.parameter(&pointer, parameter) {
+                            v2_parameters.insert(name.clone(), parameter);
+                        }
+                    }
+                    v3_0::ObjectOrReference::Ref { .. } => {
+                        self.warn(&pointer, "parameter references are not supported here")
+                    }
+                }
+            }
+            if !v2_parameters.is_empty() {
+                spec.parameters = Some(v2_parameters);
+            }
+        }
+
+        if let Some(responses) = &components.responses {
+            let mut v2_responses = BTreeMap::new();
+            for (name, response) in responses {
+                let pointer = format!("#/components/responses/{}", pointer_token(name));
+                match response {
+                    v3_0::ObjectOrReference::Object(response) => {
+                        let response = self.response(&pointer, response, &mut vec![]);
+                        v2_responses.insert(name.clone(), response);
+                    }
+                    v3_0::ObjectOrReference::Ref { .. } => {
+                        self.warn(&pointer, "response references are not supported here")
+                    }
+                }
+            }
+            if !v2_responses.is_empty() {
+                spec.responses = Some(v2_responses);
+            }
+        }
+
+        // Request bodies, headers and examples are inlined where they are referenced.
+        if components.links.as_ref().map_or(false, |l| !l.is_empty()) {
+            self.warn("#/components/links", "links are not supported");
+        }
+        if components
+            .callbacks
+            .as_ref()
+            .map_or(false, |c| !c.is_empty())
+        {
+            self.warn("#/components/callbacks", "callbacks are not supported");
+        }
+        if !components.extensions.is_empty() {
+            self.warn("#/components", "specification extensions are not supported");
+        }
+    }
+
+    fn security_scheme(
+        &mut self,
+        pointer: &str,
+        scheme: &v3_0::SecurityScheme,
+    ) -> Option<v2::Security> {
+        match scheme {
+            v3_0::SecurityScheme::ApiKey {
+                name,
+                location,
+                description,
+                ..
+            } => {
+                if location == "cookie" {
+                    self.warn(pointer, "cookie API keys are not supported");
+                    return None;
+                }
+                Some(v2::Security::ApiKey {
+                    name: name.clone(),
+                    location: location.clone(),
+                    description: description.clone(),
+                })
+            }
+            v3_0::SecurityScheme::Http {
+                scheme,
+                description,
+                ..
+            } => {
+                if scheme.eq_ignore_ascii_case("basic") {
+                    Some(v2::Security::Basic {
+                        description: description.clone(),
+                    })
+                } else if scheme.eq_ignore_ascii_case("bearer") {
+                    self.warn(
+                        pointer,
+                        "bearer authentication is declared as an API key in the Authorization header",
+                    );
+                    Some(v2::Security::ApiKey {
+                        name: "Authorization".to_string(),
+                        location: "header".to_string(),
+                        description: description.clone(),
+                    })
+                } else {
+                    self.warn(
+                        pointer,
+                        &format!(
+                            "the `{}` HTTP authentication scheme is not supported",
+                            scheme
+                        ),
+                    );
+                    None
+                }
+            }
+            v3_0::SecurityScheme::OAuth2 {
+                flows, description, ..
+            } => {
+                let mut converted = vec![];
+                if let Some(flow) = &flows.implicit {
+                    converted.push(v2::Security::Oauth2 {
+                        flow: v2::Flow::Implicit,
+                        authorization_url: flow.authorization_url.to_string(),
+                        token_url: None,
+                        scopes: flow.scopes.clone(),
+                        description: description.clone(),
+                    });
+                }
+                if let Some(flow) = &flows.password {
+                    converted.push(v2::Security::Oauth2 {
+                        flow: v2::Flow::Password,
+                        authorization_url: String::new(),
+                        token_url: Some(flow.token_url.to_string()),
+                        scopes: flow.scopes.clone(),
+                        description: description.clone(),
+                    });
+                }
+                if let Some(flow) = &flows.client_credentials {
+                    converted.push(v2::Security::Oauth2 {
+                        flow: v2::Flow::Application,
+                        authorization_url: String::new(),
+                        token_url: Some(flow.token_url.to_string()),
+                        scopes: flow.scopes.clone(),
+                        description: description.clone(),
+                    });
+                }
+                if let Some(flow) = &flows.authorization_code {
+                    converted.push(v2::Security::Oauth2 {
+                        flow: v2::Flow::AccessCode,
+                        authorization_url: flow.authorization_url.to_string(),
+                        token_url: Some(flow.token_url.to_string()),
+                        scopes: flow.scopes.clone(),
+                        description: description.clone(),
+                    });
+                }
+                if converted.len() > 1 {
+                    self.warn(pointer, "only the first OAuth flow is kept");
+                } else if converted.is_empty() {
+                    self.warn(pointer, "OAuth without flows is not supported");
+                }
+                converted.into_iter().next()
+            }
+            v3_0::SecurityScheme::OpenIdConnect { .. } => {
+                self.warn(pointer, "OpenID Connect is not supported");
+                None
+            }
+        }
+    }
+
+    /// Removes the requirements that use a security scheme which could not be converted.
+    fn security_requirements(
+        &self,
+        security: &[v3_0::SecurityRequirement],
+    ) -> Vec<v2::SecurityRequirement> {
+        security
+            .iter()
+            .filter(|requirement| {
+                requirement
+                    .keys()
+                    .all(|name| !self.dropped_schemes.contains(name))
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn path_item(&mut self, pointer: &str, item: &v3_0::PathItem) -> v2::PathItem {
+        if item.reference.is_some() {
+            self.warn(pointer, "path item references are not supported");
+        }
+        if item.summary.is_some() || item.description.is_some() {
+            self.warn(
+                pointer,
+                "path item summaries and descriptions are not supported",
+            );
+        }
+        if item.servers.is_some() {
+            self.warn(
+                &format!("{}/servers", pointer),
+                "path servers are not supported",
+            );
+        }
+        if item.trace.is_some() {
+            self.warn(
+                &format!("{}/trace", pointer),
+                "TRACE operations are not supported",
+            );
+        }
+
+        let mut operation = |method: &str, operation: &Option<v3_0::Operation>| {
+            operation
+                .as_ref()
+                .map(|operation| self.operation(&format!("{}/{}", pointer, method), operation))
+        };
+        let mut v2_item = v2::PathItem {
+            get: operation("get", &item.get),
+            post: operation("post", &item.post),
+            put: operation("put", &item.put),
+            patch: operation("patch", &item.patch),
+            delete: operation("delete", &item.delete),
+            options: operation("options", &item.options),
+            head: operation("head", &item.head),
+            parameters: None,
+            extensions: item.extensions.clone(),
+        };
+        v2_item.parameters = item
+            .parameters
+            .as_ref()
+            .map(|parameters| self.parameters(&format!("{}/parameters", pointer), parameters));
+        v2_item
+    }
+
+    fn operation(&mut self, pointer: &str, operation: &v3_0::Operation) -> v2::Operation {
+        let mut parameters = operation
+            .parameters
+            .as_ref()
+            .map(|parameters| self.parameters(&format!("{}/parameters", pointer), parameters))
+            .unwrap_or_default();
+        let mut consumes = vec![];
+        if let Some(body) = &operation.request_body {
+            let pointer = format!("{}/requestBody", pointer);
+            if let Some(body) = self.resolve(&pointer, &self.spec_request_bodies(), body) {
+                consumes = body.content.keys().cloned().collect();
+                parameters.extend(self.request_body(&pointer, body));
+            }
+        }
+
+        let mut produces = vec![];
+        let responses = operation
+            .responses
+            .iter()
+            .map(|(code, response)| {
+                let pointer = format!("{}/responses/{}", pointer, pointer_token(code));
+                (
+                    code.clone(),
+                    self.response(&pointer, response, &mut produces),
+                )
+            })
+            .collect();
+
+        if operation
+            .callbacks
+            .as_ref()
+            .map_or(false, |c| !c.is_empty())
+        {
+            self.warn(
+                &format!("{}/callbacks", pointer),
+                "callbacks are not supported",
+            );
+        }
+        if operation.servers.is_some() {
+            self.warn(
+                &format!("{}/servers", pointer),
+                "operation servers are not supported",
+            );
+        }
+        if operation.external_docs.is_some() {
+            self.warn(
+                &format!("{}/externalDocs", pointer),
+                "external docs are not supported",
+            );
+        }
+
+        v2::Operation {
+            summary: operation.summary.clone(),
+            description: operation.description.clone(),
+            consumes: non_empty(consumes),
+            produces: non_empty(produces),
+            schemes: None,
+            tags: operation.tags.clone(),
+            operation_id: operation.operation_id.clone(),
+            responses,
+            parameters: non_empty(parameters),
+            security: operation
+                .security
+                .as_ref()
+                .map(|security| self.security_requirements(security)),
+            deprecated: operation.deprecated,
+            extensions: operation.extensions.clone(),
+        }
+    }
+
+    fn parameters(
+        &mut self,
+        pointer: &str,
+        parameters: &[v3_0::ObjectOrReference<v3_0::Parameter>],
+    ) -> Vec<v2::ParameterOrRef> {
+        parameters
+            .iter()
+            .enumerate()
+            .filter_map(|(i, parameter)| {
+                let pointer = format!("{}/{}", pointer, i);
+                match parameter {
+                    v3_0::ObjectOrReference::Object(parameter) => self
+                        .parameter(&pointer, parameter)
+                        .map(v2::ParameterOrRef::from),
+                    v3_0::ObjectOrReference::Ref { ref_path } => Some(v2::ParameterOrRef::Ref {
+                        ref_path: self.reference(&pointer, ref_path),
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    fn parameter(&mut self, pointer: &str, parameter: &v3_0::Parameter) -> Option<v2::Parameter> {
+        if parameter.location == "cookie" {
+            self.warn(pointer, "cookie parameters are not supported");
+            return None;
+        }
+        let schema = match (&parameter.schema, &parameter.content) {
+            (Some(schema), _) => schema.clone(),
+            (None, Some(content)) => {
+                self.warn(
+                    &format!("{}/content", pointer),
+                    "parameter content is replaced with its schema",
+                );
+                preferred_media_type(content)
+                    .and_then(|(_, media_type)| media_type.schema.as_ref())
+                    .and_then(|schema| self.resolve(pointer, &self.spec_schemas(), schema))
+                    .cloned()
+                    .unwrap_or_default()
+            }
+            (None, None) => v3_0::Schema::default(),
+        };
+        if parameter.deprecated == Some(true) {
+            self.warn(pointer, "deprecated parameters are not supported");
+        }
+        if !parameter.extensions.is_empty() {
+            self.warn(pointer, "specification extensions are not supported");
+        }
+
+        let mut v2_parameter = self.simple_parameter(
+            &format!("{}/schema", pointer),
+            &parameter.name,
+            &parameter.location,
+            &schema,
+        );
+        v2_parameter.required = parameter.required;
+        v2_parameter.description = parameter.description.clone();
+        v2_parameter.allow_empty_value = parameter.allow_empty_value;
+        if v2_parameter.param_type.as_deref() == Some("array") {
+            v2_parameter.collection_format = match (&parameter.style, parameter.explode) {
+                (Some(v3_0::ParameterStyle::SpaceDelimited), _) => Some("ssv".to_string()),
+                (Some(v3_0::ParameterStyle::PipeDelimited), _) => Some("pipes".to_string()),
+                (Some(v3_0::ParameterStyle::Form), Some(false)) => Some("csv".to_string()),
+                // `form` explodes by default in query parameters.
+                (Some(v3_0::ParameterStyle::Form), _) | (None, _)
+                    if parameter.location == "query" && parameter.explode != Some(false) =>
+                {
+                    Some("multi".to_string())
+                }
+                _ => None,
+            };
+        }
+        if let Some(example) = parameter
+            .examples
+            .as_ref()
+            .and_then(|examples| self.example(pointer, examples))
+        {
+            v2_parameter.example = Some(example);
+        }
+        Some(v2_parameter)
+    }
+
+    /// Returns a parameter other than a body parameter, whose type is described by its own
+    /// fields rather than a schema.
+    fn simple_parameter(
+        &mut self,
+        pointer: &str,
+        name: &str,
+        location: &str,
+        schema: &v3_0::Schema,
+    ) -> v2::Parameter {
+        let mut param_type = schema
+            .schema_type
+            .clone()
+            .unwrap_or_else(|| "string".to_string());
+        if param_type == "object" {
+            self.warn(pointer, "object parameters are declared as strings");
+            param_type = "string".to_string();
+        }
+        let mut format = schema.format.clone();
+        if location == "formData" && format.as_deref() == Some("binary") {
+            param_type = "file".to_string();
+            format = None;
+        }
+        let items = schema.items.as_ref().map(|items| {
+            let pointer = format!("{}/items", pointer);
+            self.schema(&pointer, items)
+        });
+
+        v2::Parameter {
+            name: name.to_string(),
+            location: location.to_string(),
+            param_type: Some(param_type),
+            format,
+            items,
+            unique_items: schema.unique_items,
+            default: schema.default.clone(),
+            enum_values: schema.enum_values.clone(),
+            example: schema.example.clone(),
+            ..v2::Parameter::default()
+        }
+    }
+
+    /// Converts a request body into a `body` parameter, or into `formData` parameters for form
+    /// media types.
+    fn request_body(&mut self, pointer: &str, body: &v3_0::RequestBody) -> Vec<v2::ParameterOrRef> {
+        if !body.extensions.is_empty() {
+            self.warn(pointer, "specification extensions are not supported");
+        }
+        let form = body
+            .content
+            .iter()
+            .find(|(media_type, _)| is_form_media_type(media_type));
+        if let Some((media_type, content)) = form {
+            if body.content.len() > 1 {
+                self.warn(
+                    &format!("{}/content", pointer),
+                    &format!("only the form parameters of {} are kept", media_type),
+                );
+            }
+            let schema = content
+                .schema
+                .as_ref()
+                .and_then(|schema| self.resolve(pointer, &self.spec_schemas(), schema))
+                .cloned()
+                .unwrap_or_default();
+            let required = schema.required.clone().unwrap_or_default();
+            return schema
+                .properties
+                .iter()
+                .flatten()
+                .map(|(name, property)| {
+                    let pointer = format!(
+                        "{}/content/{}/schema/properties/{}",
+                        pointer,
+                        pointer_token(media_type),
+                        pointer_token(name)
+                    );
+                    let mut parameter = self.simple_parameter(&pointer, name, "formData", property);
+                    parameter.description = property.description.clone();
+                    if required.contains(name) {
+                        parameter.required = Some(true);
+                    }
+                    parameter.into()
+                })
+                .collect();
+        }
+
+        let (media_type, content) = match preferred_media_type(&body.content) {
+            Some(media_type) => media_type,
+            None => return vec![],
+        };
+        if body
+            .content
+            .values()
+            .any(|other| other.schema != content.schema)
+        {
+            self.warn(
+                &format!("{}/content", pointer),
+                &format!("only the schema of {} is kept", media_type),
+            );
+        }
+        let schema = content.schema.as_ref().map(|schema| {
+            let pointer = format!("{}/content/{}/schema", pointer, pointer_token(media_type));
+            self.schema_or_reference(&pointer, schema)
+        });
+        vec![v2::Parameter {
+            name: "body".to_string(),
+            location: "body".to_string(),
+            required: body.required,
+            description: body.description.clone(),
+            schema: Some(schema.unwrap_or_default()),
+            ..v2::Parameter::default()
+        }
+        .into()]
+    }
+
+    /// Converts a response, adding its media types to `produces`.
+    fn response(
+        &mut self,
+        pointer: &str,
+        response: &v3_0::Response,
+        produces: &mut Vec<String>,
+    ) -> v2::Response {
+        let mut v2_response = v2::Response {
+            description: response.description.clone().unwrap_or_default(),
+            ..v2::Response::default()
+        };
+
+        if let Some(content) = &response.content {
+            for media_type in content.keys() {
+                if !produces.contains(media_type) {
+                    produces.push(media_type.clone());
+                }
+            }
+            if let Some((media_type, preferred)) = preferred_media_type(content) {
+                if content
+                    .values()
+                    .any(|other| other.schema != preferred.schema)
+                {
+                    self.warn(
+                        &format!("{}/content", pointer),
+                        &format!("only the schema of {} is kept", media_type),
+                    );
+                }
+                v2_response.schema = preferred.schema.as_ref().map(|schema| {
+                    let pointer =
+                        format!("{}/content/{}/schema", pointer, pointer_token(media_type));
+                    self.schema_or_reference(&pointer, schema)
+                });
+            }
+            let mut examples = BTreeMap::new();
+            for (media_type, media) in content {
+                let pointer = format!("{}/content/{}", pointer, pointer_token(media_type));
+                if let Some(example) = media
+                    .examples
+                    .as_ref()
+                    .and_then(|examples| self.example(&pointer, examples))
+                {
+                    examples.insert(media_type.clone(), example);
+                }
+            }
+            if !examples.is_empty() {
+                v2_response.examples = Some(examples);
+            }
+        }
+
+        if let Some(headers) = &response.headers {
+            let mut v2_headers = BTreeMap::new();
+            for (name, header) in headers {
+                let pointer = format!("{}/headers/{}", pointer, pointer_token(name));
+                if let Some(header) = self.resolve(&pointer, &self.spec_headers(), header) {
+                    let header = self.header(&pointer, header);
+                    v2_headers.insert(name.clone(), header);
+                }
+            }
+            v2_response.headers = non_empty_map(v2_headers);
+        }
+
+        if response.links.as_ref().map_or(false, |l| !l.is_empty()) {
+            self.warn(&format!("{}/links", pointer), "links are not supported");
+        }
+        v2_response.extensions = response.extensions.clone();
+        v2_response
+    }
+
+    fn header(&mut self, pointer: &str, header: &v3_0::Header) -> v2::Header {
+        let schema = header.schema.clone().unwrap_or_default();
+        let items = schema.items.as_ref().map(|items| {
+            let pointer = format!("{}/schema/items", pointer);
+            self.schema(&pointer, items)
+        });
+        v2::Header {
+            description: header.description.clone(),
+            header_type: schema.schema_type.unwrap_or_else(|| "string".to_string()),
+            format: schema.format,
+            items,
+        }
+    }
+
+    /// Returns the value of an example, keeping only the first of several named examples.
+    fn example(
+        &mut self,
+        pointer: &str,
+        examples: &v3_0::MediaTypeExample,
+    ) -> Option<serde_json::Value> {
+        match examples {
+            v3_0::MediaTypeExample::Example { example } => Some(example.clone()),
+            v3_0::MediaTypeExample::Examples { examples } => {
+                if examples.len() > 1 {
+                    self.warn(
+                        &format!("{}/examples", pointer),
+                        "only the first example is kept",
+                    );
+                }
+                let (name, example) = examples.iter().next()?;
+                let pointer = format!("{}/examples/{}", pointer, pointer_token(name));
+                let example = self.resolve(&pointer, &self.spec_examples(), example)?;
+                if example.value.is_none() && example.external_value.is_some() {
+                    self.warn(&pointer, "external examples are not supported");
+                }
+                example.value.clone()
+            }
+        }
+    }
+
+    fn schema_or_reference(
+        &mut self,
+        pointer: &str,
+        schema: &v3_0::ObjectOrReference<v3_0::Schema>,
+    ) -> v2::Schema {
+        match schema {
+            v3_0::ObjectOrReference::Object(schema) => self.schema(pointer, schema),
+            v3_0::ObjectOrReference::Ref { ref_path } => v2::Schema {
+                ref_path: Some(self.reference(pointer, ref_path)),
+                ..v2::Schema::default()
+            },
+        }
+    }
+
+    /// Converts a schema through its JSON representation, since most keywords are the same in
+    /// both versions.
+    fn schema(&mut self, pointer: &str, schema: &v3_0::Schema) -> v2::Schema {
+        let mut value = serde_json::to_value(schema).unwrap_or(serde_json::Value::Null);
+        self.schema_value(pointer, &mut value);
+        serde_json::from_value(value).unwrap_or_default()
+    }
+
+    fn schema_value(&mut self, pointer: &str, value: &mut serde_json::Value) {
+        let object = match value.as_object_mut() {
+            Some(object) => object,
+            None => return,
+        };
+
+        if let Some(serde_json::Value::String(ref_path)) = object.get("$ref") {
+            let ref_path = self.reference(pointer, ref_path);
+            object.insert("$ref".to_string(), serde_json::Value::String(ref_path));
+        }
+        if let Some(nullable) = object.remove("nullable") {
+            object.insert("x-nullable".to_string(), nullable);
+        }
+        if let Some(discriminator) = object.remove("discriminator") {
+            if discriminator.get("mapping").is_some() {
+                self.warn(pointer, "discriminator mappings are not supported");
+            }
+            if let Some(property_name) = discriminator.get("propertyName") {
+                object.insert("discriminator".to_string(), property_name.clone());
+            }
+        }
+        for keyword in &["oneOf", "anyOf", "not", "writeOnly", "deprecated"] {
+            if object.remove(*keyword).is_some() {
+                self.warn(pointer, &format!("`{}` is not supported", keyword));
+            }
+        }
+
+        if let Some(serde_json::Value::Object(properties)) = object.get_mut("properties") {
+            for (name, property) in properties.iter_mut() {
+                let pointer = format!("{}/properties/{}", pointer, pointer_token(name));
+                self.schema_value(&pointer, property);
+            }
+        }
+        if let Some(serde_json::Value::Array(all_of)) = object.get_mut("allOf") {
+            for (i, schema) in all_of.iter_mut().enumerate() {
+                self.schema_value(&format!("{}/allOf/{}", pointer, i), schema);
+            }
+        }
+        if let Some(items) = object.get_mut("items") {
+            self.schema_value(&format!("{}/items", pointer), items);
+        }
+        if let Some(additional_properties) = object.get_mut("additionalProperties") {
+            let pointer = format!("{}/additionalProperties", pointer);
+            self.schema_value(&pointer, additional_properties);
+        }
+    }
+
+    /// Rewrites a reference to a component into a reference to its Swagger 2.0 equivalent.
+    fn reference(&mut self, pointer: &str, ref_path: &str) -> String {
+        let prefixes = [
+            ("#/components/schemas/", "#/definitions/"),
+            ("#/components/parameters/", "#/parameters/"),
+            ("#/components/responses/", "#/responses/"),
+        ];
+        for (from, to) in prefixes.iter() {
+            if let Some(name) = ref_path.strip_prefix(from) {
+                return format!("{}{}", to, name);
+            }
+        }
+        if ref_path.starts_with("#/") {
+            self.warn(
+                pointer,
+                &format!("the reference {} cannot be converted", ref_path),
+            );
+        }
+        ref_path.to_string()
+    }
+
+    /// Follows references to components, warning about those that cannot be resolved.
+    fn resolve<'b, T>(
+        &mut self,
+        pointer: &str,
+        components: &(
+            &'static str,
+            Option<&'b BTreeMap<String, v3_0::ObjectOrReference<T>>>,
+        ),
+        object: &'b v3_0::ObjectOrReference<T>,
+    ) -> Option<&'b T> {
+        let (prefix, components) = components;
+        let mut object = object;
+        // References may be chained, but not circular.
+        for _ in 0..=components.map_or(0, |components| components.len()) {
+            match object {
+                v3_0::ObjectOrReference::Object(object) => return Some(object),
+                v3_0::ObjectOrReference::Ref { ref_path } => {
+                    match ref_path
+                        .strip_prefix(prefix)
+                        .and_then(|name| components.and_then(|c| c.get(name)))
+                    {
+                        Some(component) => object = component,
+                        None => break,
+                    }
+                }
+            }
+        }
+        self.warn(pointer, "unresolvable reference");
+        None
+    }
+
+    fn spec_schemas(
+        &self,
+    ) -> (
+        &'static str,
+        Option<&'a BTreeMap<String, v3_0::ObjectOrReference<v3_0::Schema>>>,
+    ) {
+        let spec = self.spec;
+        (
+            "#/components/schemas/",
+            spec.components.as_ref().and_then(|c| c.schemas.as_ref()),
+        )
+    }
+
+    fn spec_request_bodies(
+        &self,
+    ) -> (
+        &'static str,
+        Option<&'a BTreeMap<String, v3_0::ObjectOrReference<v3_0::RequestBody>>>,
+    ) {
+        let spec = self.spec;
+        (
+            "#/components/requestBodies/",
+            spec.components
+                .as_ref()
+                .and_then(|c| c.request_bodies.as_ref()),
+        )
+    }
+
+    fn spec_headers(
+        &self,
+    ) -> (
+        &'static str,
+        Option<&'a BTreeMap<String, v3_0::ObjectOrReference<v3_0::Header>>>,
+    ) {
+        let spec = self.spec;
+        (
+            "#/components/headers/",
+            spec.components.as_ref().and_then(|c| c.headers.as_ref()),
+        )
+    }
+
+    fn spec_examples(
+        &self,
+    ) -> (
+        &'static str,
+        Option<&'a BTreeMap<String, v3_0::ObjectOrReference<v3_0::Example>>>,
+    ) {
+        let spec = self.spec;
+        (
+            "#/components/examples/",
+            spec.components.as_ref().and_then(|c| c.examples.as_ref()),
+        )
+    }
+}
+
+/// Converts a Swagger 2.0 specification to OpenAPI 3.0.
+struct Upgrade<'a> {
+    spec: &'a v2::Spec,
+    warnings: Vec<String>,
+}
+
+/// The parameters of an operation, sorted by where they belong in OpenAPI 3.0.
+#[derive(Clone, Default)]
+struct UpgradedParameters {
+    parameters: Vec<v3_0::ObjectOrReference<v3_0::Parameter>>,
+    body: Option<v3_0::ObjectOrReference<v3_0::RequestBody>>,
+    /// Form parameters, which are collected into a single request body.
+    form: Vec<v2::Parameter>,
+}
+
+impl<'a> Upgrade<'a> {
+    fn warn(&mut self, pointer: &str, message: &str) {
+        self.warnings.push(format!("{}: {}", pointer, message));
+    }
+
+    fn spec(&mut self) -> v3_0::Spec {
+        let spec = self.spec;
+        let schemes = spec
+            .schemes
+            .iter()
+            .flatten()
+            .map(|scheme| scheme_name(scheme).to_string())
+            .collect::<Vec<_>>();
+        let mut v3_spec = v3_0::Spec {
+            openapi: "3.0.3".to_string(),
+            info: self.info(&spec.info),
+            servers: self.servers(&schemes),
+            components: self.components(),
+            security: spec.security.clone(),
+            extensions: spec.extensions.clone(),
+            ..v3_0::Spec::default()
+        };
+
+        for (path, item) in &spec.paths {
+            let pointer = format!("#/paths/{}", pointer_token(path));
+            let item = self.path_item(&pointer, item);
+            v3_spec.paths.insert(path.clone(), item);
+        }
+
+        v3_spec.tags = spec.tags.as_ref().map(|tags| {
+            tags.iter()
+                .enumerate()
+                .map(|(i, tag)| {
+                    if tag.external_docs.is_some() {
+                        self.warn(
+                            &format!("#/tags/{}/externalDocs", i),
+                            "tag external docs are not supported",
+                        );
+                    }
+                    v3_0::Tag {
+                        name: tag.name.clone(),
+                        description: tag.description.clone(),
+                        ..v3_0::Tag::default()
+                    }
+                })
+                .collect()
+        });
+        if let Some(external_docs) = &spec.external_docs {
+            if external_docs.len() > 1 {
+                self.warn("#/externalDocs", "only the first external docs are kept");
+            }
+            v3_spec.external_docs = external_docs.first().and_then(|docs| {
+                let url = self.url("#/externalDocs/0/url", &docs.url)?;
+                Some(v3_0::ExternalDoc {
+                    url,
+                    description: docs.description.clone(),
+                    extensions: BTreeMap::new(),
+                })
+            });
+        }
+
+        v3_spec
+    }
+
+    fn info(&mut self, info: &v2::Info) -> v3_0::Info {
+        v3_0::Info {
+            title: info.title.clone().unwrap_or_default(),
+            description: info.description.clone(),
+            terms_of_service: info
+                .terms_of_service
+                .as_ref()
+                .and_then(|url| self.url("#/info/termsOfService", url)),
+            version: info.version.clone().unwrap_or_default(),
+            contact: info.contact.as_ref().map(|contact| v3_0::Contact {
+                name: contact.name.clone(),
+                url: contact
+                    .url
+                    .as_ref()
+                    .and_then(|url| self.url("#/info/contact/url", url)),
+                email: contact.email.clone(),
+                extensions: BTreeMap::new(),
+            }),
+            license: info.license.as_ref().and_then(|license| {
+                let name = match &license.name {
+                    Some(name) => name.clone(),
+                    None => {
+                        self.warn(
+                            "#/info/license",
+                            "licenses without a name are not supported",
+                        );
+                        return None;
+                    }
+                };
+                Some(v3_0::License {
+                    name,
+                    url: license
+                        .url
+                        .as_ref()
+                        .and_then(|url| self.url("#/info/license/url", url)),
+                    extensions: BTreeMap::new(),
+                })
+            }),
+            extensions: info.extensions.clone(),
+        }
+    }
+
+    /// Maps `host` and `basePath` to a server for each of the schemes.
+    fn servers(&self, schemes: &[String]) -> Option<Vec<v3_0::Server>> {
+        let spec = self.spec;
+        let base_path = spec
+            .base_path
+            .as_deref()
+            .unwrap_or_default()
+            .trim_end_matches('/');
+        let urls = match &spec.host {
+            Some(host) if schemes.is_empty() => vec![format!("//{}{}", host, base_path)],
+            Some(host) => schemes
+                .iter()
+                .map(|scheme| format!("{}://{}{}", scheme, host, base_path))
+                .collect(),
+            None if base_path.is_empty() => vec![],
+            None => vec![base_path.to_string()],
+        };
+        non_empty(
+            urls.into_iter()
+                .map(|url| v3_0::Server {
+                    url,
+                    ..v3_0::Server::default()
+                })
+                .collect(),
+        )
+    }
+
+    fn components(&mut self) -> Option<v3_0::Components> {
+        let spec = self.spec;
+        let mut components = v3_0::Components::default();
+
+        if let Some(definitions) = &spec.definitions {
+            let schemas = definitions
+                .iter()
+                .map(|(name, schema)| {
+                    let pointer = format!("#/definitions/{}", pointer_token(name));
+                    (name.clone(), self.schema_or_reference(&pointer, schema))
+                })
+                .collect();
+            components.schemas = Some(schemas);
+        }
+
+        if let Some(parameters) = &spec.parameters {
+            let consumes = self.media_types(None, &spec.consumes);
+            let mut v3_parameters = BTreeMap::new();
+            let mut request_bodies = BTreeMap::new();
+            for (name, parameter) in parameters {
+                let pointer = format!("#/parameters/{}", pointer_token(name));
+                match parameter.location.as_str() {
+                    "body" => {
+                        let body = self.request_body(&pointer, parameter, &consumes);
+                        request_bodies.insert(name.clone(), v3_0::ObjectOrReference::Object(body));
+                    }
+                    // Form parameters are inlined into the request bodies that use them.
+                    "formData" => {}
+                    _ => {
+                        let parameter = self.parameter(&pointer, parameter);
+                        v3_parameters
+                            .insert(name.clone(), v3_0::ObjectOrReference::Object(parameter));
+                    }
+                }
+            }
+            components.parameters = non_empty_map(v3_parameters);
+            components.request_bodies = non_empty_map(request_bodies);
+        }
+
+        if let Some(responses) = &spec.responses {
+            let produces = self.media_types(None, &spec.produces);
+            let responses = responses
+                .iter()
+                .map(|(name, response)| {
+                    let pointer = format!("#/responses/{}", pointer_token(name));
+                    let response = self.response(&pointer, response, &produces);
+                    (name.clone(), v3_0::ObjectOrReference::Object(response))
+                })
+                .collect();
+            components.responses = Some(responses);
+        }
+
+        if let Some(definitions) = &spec.security_definitions {
+            let mut schemes = BTreeMap::new();
+            for (name, security) in definitions {
+                let pointer = format!("#/securityDefinitions/{}", pointer_token(name));
+                if let Some(scheme) = self.security_scheme(&pointer, security) {
+                    schemes.insert(name.clone(), v3_0::ObjectOrReference::Object(scheme));
+                }
+            }
+            components.security_schemes = non_empty_map(schemes);
+        }
+
+        if components == v3_0::Components::default() {
+            None
+        } else {
+            Some(components)
+        }
+    }
+
+    fn security_scheme(
+        &mut self,
+        pointer: &str,
+        security: &v2::Security,
+    ) -> Option<v3_0::SecurityScheme> {
+        match security {
+            v2::Security::ApiKey {
+                name,
+                location,
+                description,
+            } => Some(v3_0::SecurityScheme::ApiKey {
+                name: name.clone(),
+                location: location.clone(),
+                description: description.clone(),
+                extensions: BTreeMap::new(),
+            }),
+            v2::Security::Basic { description } => Some(v3_0::SecurityScheme::Http {
+                scheme: "basic".to_string(),
+                bearer_format: None,
+                description: description.clone(),
+                extensions: BTreeMap::new(),
+            }),
+            v2::Security::Oauth2 {
+                flow,
+                authorization_url,
+                token_url,
+                scopes,
+                description,
+            } => {
+                let authorization_url =
+                    || Url::parse(authorization_url).map_err(|_| "authorizationUrl".to_string());
+                let token_url = || {
+                    token_url
+                        .as_deref()
+                        .ok_or_else(|| "tokenUrl".to_string())
+                        .and_then(|url| Url::parse(url).map_err(|_| "tokenUrl".to_string()))
+                };
+                let scopes = scopes.clone();
+                let mut flows = v3_0::Flows::default();
+                let converted = match flow {
+                    v2::Flow::Implicit => authorization_url().map(|authorization_url| {
+                        flows.implicit = Some(v3_0::ImplicitFlow {
+                            authorization_url,
+                            refresh_url: None,
+                            scopes,
+                            extensions: BTreeMap::new(),
+                        })
+                    }),
+                    v2::Flow::Password => token_url().map(|token_url| {
+                        flows.password = Some(v3_0::PasswordFlow {
+                            token_url,
+                            refresh_url: None,
+                            scopes,
+                            extensions: BTreeMap::new(),
+                        })
+                    }),
+                    v2::Flow::Application => token_url().map(|token_url| {
+                        flows.client_credentials = Some(v3_0::ClientCredentialsFlow {
+                            token_url,
+                            refresh_url: None,
+                            scopes,
+                            extensions: BTreeMap::new(),
+                        })
+                    }),
+                    v2::Flow::AccessCode => authorization_url().and_then(|authorization_url| {
+                        let token_url = token_url()?;
+                        flows.authorization_code = Some(v3_0::AuthorizationCodeFlow {
+                            authorization_url,
+                            token_url,
+                            refresh_url: None,
+                            scopes,
+                            extensions: BTreeMap::new(),
+                        });
+                        Ok(())
+                    }),
+                };
+                if let Err(field) = converted {
+                    self.warn(
+                        &format!("{}/{}", pointer, field),
+                        "OAuth flows need an absolute URL",
+                    );
+                    return None;
+                }
+                Some(v3_0::SecurityScheme::OAuth2 {
+                    flows: Box::new(flows),
+                    description: description.clone(),
+                    extensions: BTreeMap::new(),
+                })
+            }
+        }
+    }
+
+    fn path_item(&mut self, pointer: &str, item: &v2::PathItem) -> v3_0::PathItem {
+        let mut inherited = UpgradedParameters::default();
+        if let Some(parameters) = &item.parameters {
+            let pointer = format!("{}/parameters", pointer);
+            self.parameters(&pointer, parameters, &mut inherited);
+        }
+        // Body and form parameters apply to each operation, whereas the others stay here.
+        let parameters = std::mem::take(&mut inherited.parameters);
+
+        let mut operation = |method: &str, operation: &Option<v2::Operation>| {
+            operation.as_ref().map(|operation| {
+                let pointer = format!("{}/{}", pointer, method);
+                self.operation(&pointer, operation, inherited.clone())
+            })
+        };
+        v3_0::PathItem {
+            get: operation("get", &item.get),
+            put: operation("put", &item.put),
+            post: operation("post", &item.post),
+            delete: operation("delete", &item.delete),
+            options: operation("options", &item.options),
+            head: operation("head", &item.head),
+            patch: operation("patch", &item.patch),
+            parameters: non_empty(parameters),
+            extensions: item.extensions.clone(),
+            ..v3_0::PathItem::default()
+        }
+    }
+
+    fn operation(
+        &mut self,
+        pointer: &str,
+        operation: &v2::Operation,
+        mut parameters: UpgradedParameters,
+    ) -> v3_0::Operation {
+        let spec = self.spec;
+        if let Some(operation_parameters) = &operation.parameters {
+            let pointer = format!("{}/parameters", pointer);
+            self.parameters(&pointer, operation_parameters, &mut parameters);
+        }
+
+        let consumes = self.media_types(operation.consumes.as_ref(), &spec.consumes);
+        let request_body = if parameters.form.is_empty() {
+            parameters.body
+        } else {
+            if parameters.body.is_some() {
+                self.warn(
+                    &format!("{}/parameters", pointer),
+                    "body parameters cannot be combined with form parameters",
+                );
+            }
+            let body = self.form_request_body(pointer, &parameters.form, &consumes);
+            Some(v3_0::ObjectOrReference::Object(body))
+        };
+
+        let produces = self.media_types(operation.produces.as_ref(), &spec.produces);
+        let responses = operation
+            .responses
+            .iter()
+            .map(|(code, response)| {
+                let pointer = format!("{}/responses/{}", pointer, pointer_token(code));
+                (code.clone(), self.response(&pointer, response, &produces))
+            })
+            .collect();
+
+        v3_0::Operation {
+            tags: operation.tags.clone(),
+            summary: operation.summary.clone(),
+            description: operation.description.clone(),
+            operation_id: operation.operation_id.clone(),
+            parameters: non_empty(parameters.parameters),
+            request_body,
+            responses,
+            deprecated: operation.deprecated,
+            security: operation.security.clone(),
+            servers: operation
+                .schemes
+                .as_ref()
+                .and_then(|schemes| self.servers(schemes)),
+            extensions: operation.extensions.clone(),
+            ..v3_0::Operation::default()
+        }
+    }
+
+    /// Sorts parameters into `parameters`, replacing those with the same name and location.
+    fn parameters(
+        &mut self,
+        pointer: &str,
+        parameters: &[v2::ParameterOrRef],
+        upgraded: &mut UpgradedParameters,
+    ) {
+        let spec = self.spec;
+        let consumes = self.media_types(None, &spec.consumes);
+        for (i, parameter) in parameters.iter().enumerate() {
+            let pointer = format!("{}/{}", pointer, i);
+            let (parameter, reference) = match parameter {
+                v2::ParameterOrRef::Ref { ref_path } => {
+                    let component = ref_path.strip_prefix("#/parameters/").and_then(|name| {
+                        spec.parameters
+                            .as_ref()
+                            .and_then(|parameters| parameters.get(name))
+                            .map(|parameter| (name, parameter.clone()))
+                    });
+                    match component {
+                        Some((name, parameter)) => (parameter, Some(name)),
+                        None => {
+                            self.warn(&pointer, "unresolvable reference");
+                            continue;
+                        }
+                    }
+                }
+                parameter => (inline_parameter(parameter), None),
+            };
+
+            match parameter.location.as_str() {
+                "body" => {
+                    upgraded.body = Some(match reference {
+                        Some(name) => v3_0::ObjectOrReference::Ref {
+                            ref_path: format!("#/components/requestBodies/{}", name),
+                        },
+                        None => v3_0::ObjectOrReference::Object(
+                            self.request_body(&pointer, &parameter, &consumes),
+                        ),
+                    });
+                }
+                "formData" => {
+                    upgraded.form.retain(|form| form.name != parameter.name);
+                    upgraded.form.push(parameter);
+                }
+                _ => {
+                    let v3_parameter = self.parameter(&pointer, &parameter);
+                    upgraded.parameters.retain(|existing| match existing {
+                        v3_0::ObjectOrReference::Object(existing) => {
+                            existing.name != v3_parameter.name
+                                || existing.location != v3_parameter.location
+                        }
+                        v3_0::ObjectOrReference::Ref { .. } => true,
+                    });
+                    upgraded.parameters.push(match reference {
+                        Some(name) => v3_0::ObjectOrReference::Ref {
+                            ref_path: format!("#/components/parameters/{}", name),
+                        },
+                        None => v3_0::ObjectOrReference::Object(v3_parameter),
+                    });
+                }
+            }
+        }
+    }
+
+    fn parameter(&mut self, pointer: &str, parameter: &v2::Parameter) -> v3_0::Parameter {
+        let (style, explode) = if parameter.param_type.as_deref() == Some("array") {
+            let query = parameter.location == "query";
+            match parameter.collection_format.as_deref().unwrap_or("csv") {
+                "csv" if query => (Some(v3_0::ParameterStyle::Form), Some(false)),
+                "csv" => (None, None),
+                "ssv" => (Some(v3_0::ParameterStyle::SpaceDelimited), None),
+                "pipes" => (Some(v3_0::ParameterStyle::PipeDelimited), None),
+                "multi" => (Some(v3_0::ParameterStyle::Form), Some(true)),
+                format => {
+                    self.warn(
+                        &format!("{}/collectionFormat", pointer),
+                        &format!("the `{}` collection format is not supported", format),
+                    );
+                    (None, None)
+                }
+            }
+        } else {
+            (None, None)
+        };
+
+        v3_0::Parameter {
+            name: parameter.name.clone(),
+            location: parameter.location.clone(),
+            required: parameter.required,
+            schema: Some(self.parameter_schema(pointer, parameter)),
+            description: parameter.description.clone(),
+            allow_empty_value: parameter.allow_empty_value,
+            style,
+            explode,
+            examples: parameter
+                .example
+                .clone()
+                .map(|example| v3_0::MediaTypeExample::Example { example }),
+            ..v3_0::Parameter::default()
+        }
+    }
+
+    /// Returns the schema of a parameter other than a body parameter, whose type is described by
+    /// its own fields.
+    fn parameter_schema(&mut self, pointer: &str, parameter: &v2::Parameter) -> v3_0::Schema {
+        let mut schema = v3_0::Schema {
+            schema_type: parameter.param_type.clone(),
+            format: parameter.format.clone(),
+            description: if parameter.location == "formData" {
+                parameter.description.clone()
+            } else {
+                None
+            },
+            enum_values: parameter.enum_values.clone(),
+            default: parameter.default.clone(),
+            unique_items: parameter.unique_items,
+            items: parameter.items.as_ref().map(|items| {
+                let pointer = format!("{}/items", pointer);
+                Box::new(self.schema(&pointer, items))
+            }),
+            ..v3_0::Schema::default()
+        };
+        if schema.schema_type.as_deref() == Some("file") {
+            schema.schema_type = Some("string".to_string());
+            schema.format = Some("binary".to_string());
+        }
+        schema
+    }
+
+    /// Converts a `body` parameter into a request body with its schema for each media type.
+    fn request_body(
+        &mut self,
+        pointer: &str,
+        parameter: &v2::Parameter,
+        consumes: &[String],
+    ) -> v3_0::RequestBody {
+        let schema = parameter.schema.as_ref().map(|schema| {
+            let pointer = format!("{}/schema", pointer);
+            self.schema_or_reference(&pointer, schema)
+        });
+        v3_0::RequestBody {
+            description: parameter.description.clone(),
+            content: consumes
+                .iter()
+                .map(|media_type| {
+                    let media = v3_0::MediaType {
+                        schema: schema.clone(),
+                        ..v3_0::MediaType::default()
+                    };
+                    (media_type.clone(), media)
+                })
+                .collect(),
+            required: parameter.required,
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    /// Converts `formData` parameters into a request body with an object schema.
+    fn form_request_body(
+        &mut self,
+        pointer: &str,
+        parameters: &[v2::Parameter],
+        consumes: &[String],
+    ) -> v3_0::RequestBody {
+        let mut properties = BTreeMap::new();
+        let mut required = vec![];
+        for parameter in parameters {
+            let pointer = format!("{}/parameters/{}", pointer, pointer_token(&parameter.name));
+            properties.insert(
+                parameter.name.clone(),
+                self.parameter_schema(&pointer, parameter),
+            );
+            if parameter.required == Some(true) {
+                required.push(parameter.name.clone());
+            }
+        }
+        let schema = v3_0::Schema {
+            schema_type: Some("object".to_string()),
+            properties: Some(properties),
+            required: non_empty(required),
+            ..v3_0::Schema::default()
+        };
+
+        let mut media_types = consumes
+            .iter()
+            .filter(|media_type| is_form_media_type(media_type))
+            .cloned()
+            .collect::<Vec<_>>();
+        if media_types.is_empty() {
+            let files = parameters
+                .iter()
+                .any(|parameter| parameter.param_type.as_deref() == Some("file"));
+            media_types.push(if files {
+                "multipart/form-data".to_string()
+            } else {
+                "application/x-www-form-urlencoded".to_string()
+            });
+        }
+        v3_0::RequestBody {
+            content: media_types
+                .into_iter()
+                .map(|media_type| {
+                    let media = v3_0::MediaType {
+                        schema: Some(v3_0::ObjectOrReference::Object(schema.clone())),
+                        ..v3_0::MediaType::default()
+                    };
+                    (media_type, media)
+                })
+                .collect(),
+            ..v3_0::RequestBody::default()
+        }
+    }
+
+    fn response(
+        &mut self,
+        pointer: &str,
+        response: &v2::Response,
+        produces: &[String],
+    ) -> v3_0::Response {
+        let schema = response.schema.as_ref().map(|schema| {
+            let pointer = format!("{}/schema", pointer);
+            self.schema_or_reference(&pointer, schema)
+        });
+        let mut content = BTreeMap::new();
+        if schema.is_some() {
+            for media_type in produces {
+                let media = v3_0::MediaType {
+                    schema: schema.clone(),
+                    ..v3_0::MediaType::default()
+                };
+                content.insert(media_type.clone(), media);
+            }
+        }
+        for (media_type, example) in response.examples.iter().flatten() {
+            let media = content
+                .entry(media_type.clone())
+                .or_insert_with(|| v3_0::MediaType {
+                    schema: schema.clone(),
+                    ..v3_0::MediaType::default()
+                });
+            media.examples = Some(v3_0::MediaTypeExample::Example {
+                example: example.clone(),
+            });
+        }
+
+        let headers = response.headers.as_ref().map(|headers| {
+            headers
+                .iter()
+                .map(|(name, header)| {
+                    let pointer = format!("{}/headers/{}", pointer, pointer_token(name));
+                    let header = self.header(&pointer, header);
+                    (name.clone(), v3_0::ObjectOrReference::Object(header))
+                })
+                .collect()
+        });
+
+        v3_0::Response {
+            description: Some(response.description.clone()),
+            headers,
+            content: non_empty_map(content),
+            links: None,
+            extensions: response.extensions.clone(),
+        }
+    }
+
+    fn header(&mut self, pointer: &str, header: &v2::Header) -> v3_0::Header {
+        let schema = v3_0::Schema {
+            schema_type: Some(header.header_type.clone()),
+            format: header.format.clone(),
+            items: header.items.as_ref().map(|items| {
+                let pointer = format!("{}/items", pointer);
+                Box::new(self.schema(&pointer, items))
+            }),
+            ..v3_0::Schema::default()
+        };
+        v3_0::Header {
+            description: header.description.clone(),
+            schema: Some(schema),
+            ..v3_0::Header::default()
+        }
+    }
+
+    /// Returns the media types of an operation, defaulting to those of the specification and
+    /// then to JSON.
+    fn media_types(
+        &self,
+        operation: Option<&Vec<String>>,
+        spec: &Option<Vec<String>>,
+    ) -> Vec<String> {
+        operation
+            .or(spec.as_ref())
+            .filter(|media_types| !media_types.is_empty())
+            .cloned()
+            .unwrap_or_else(|| vec!["application/json".to_string()])
+    }
+
+    /// Converts a schema, using a reference object when it is only a `$ref`.
+    fn schema_or_reference(
+        &mut self,
+        pointer: &str,
+        schema: &v2::Schema,
+    ) -> v3_0::ObjectOrReference<v3_0::Schema> {
+        let mut schema = self.schema(pointer, schema);
+        match schema.ref_path.take() {
+            Some(ref_path) if schema == v3_0::Schema::default() => {
+                v3_0::ObjectOrReference::Ref { ref_path }
+            }
+            ref_path => {
+                schema.ref_path = ref_path;
+                v3_0::ObjectOrReference::Object(schema)
+            }
+        }
+    }
+
+    /// Converts a schema through its JSON representation, since most keywords are the same in
+    /// both versions.
+    fn schema(&mut self, pointer: &str, schema: &v2::Schema) -> v3_0::Schema {
+        let mut value = serde_json::to_value(schema).unwrap_or(serde_json::Value::Null);
+        self.schema_value(pointer, &mut value);
+        serde_json::from_value(value).unwrap_or_default()
+    }
+
+    fn schema_value(&mut self, pointer: &str, value: &mut serde_json::Value) {
+        let object = match value.as_object_mut() {
+            Some(object) => object,
+            None => return,
+        };
+
+        if let Some(serde_json::Value::String(ref_path)) = object.get("$ref") {
+            let ref_path = self.reference(pointer, ref_path);
+            object.insert("$ref".to_string(), serde_json::Value::String(ref_path));
+        }
+        if let Some(nullable) = object.remove("x-nullable") {
+            object.insert("nullable".to_string(), nullable);
+        }
+        if let Some(serde_json::Value::String(property_name)) = object.remove("discriminator") {
+            object.insert(
+                "discriminator".to_string(),
+                serde_json::json!({ "propertyName": property_name }),
+            );
+        }
+        if object.get("type").and_then(|t| t.as_str()) == Some("file") {
+            object.insert("type".to_string(), "string".into());
+            object.insert("format".to_string(), "binary".into());
+        }
+
+        if let Some(serde_json::Value::Object(properties)) = object.get_mut("properties") {
+            for (name, property) in properties.iter_mut() {
+                let pointer = format!("{}/properties/{}", pointer, pointer_token(name));
+                self.schema_value(&pointer, property);
+            }
+        }
+        if let Some(serde_json::Value::Array(all_of)) = object.get_mut("allOf") {
+            for (i, schema) in all_of.iter_mut().enumerate() {
+                self.schema_value(&format!("{}/allOf/{}", pointer, i), schema);
+            }
+        }
+        if let Some(items) = object.get_mut("items") {
+            self.schema_value(&format!("{}/items", pointer), items);
+        }
+        if let Some(additional_properties) = object.get_mut("additionalProperties") {
+            let pointer = format!("{}/additionalProperties", pointer);
+            self.schema_value(&pointer, additional_properties);
+        }
+    }
+
+    /// Rewrites a reference to a definition into a reference to its OpenAPI 3.0 component.
+    fn reference(&mut self, pointer: &str, ref_path: &str) -> String {
+        let prefixes = [
+            ("#/definitions/", "#/components/schemas/"),
+            ("#/parameters/", "#/components/parameters/"),
+            ("#/responses/", "#/components/responses/"),
+        ];
+        for (from, to) in prefixes.iter() {
+            if let Some(name) = ref_path.strip_prefix(from) {
+                return format!("{}{}", to, name);
+            }
+        }
+        if ref_path.starts_with("#/") {
+            self.warn(
+                pointer,
+                &format!("the reference {} cannot be converted", ref_path),
+            );
+        }
+        ref_path.to_string()
+    }
+
+    fn url(&mut self, pointer: &str, url: &str) -> Option<Url> {
+        match Url::parse(url) {
+            Ok(url) => Some(url),
+            Err(_) => {
+                self.warn(pointer, &format!("{} is not an absolute URL", url));
+                None
+            }
+        }
+    }
+}
+
+/// Returns an inline parameter as a `v2::Parameter`.
+fn inline_parameter(parameter: &v2::ParameterOrRef) -> v2::Parameter {
+    match parameter.clone() {
+        v2::ParameterOrRef::Parameter {
+            name,
+            location,
+            required,
+            schema,
+            unique_items,
+            param_type,
+            format,
+            description,
+            collection_format,
+            default,
+            enum_values,
+            allow_empty_value,
+            items,
+            example,
+            ..
+        } => v2::Parameter {
+            name,
+            location,
+            required,
+            schema,
+            unique_items,
+            param_type,
+            format,
+            description,
+            items,
+            collection_format,
+            default,
+            enum_values,
+            allow_empty_value,
+            example,
+        },
+        v2::ParameterOrRef::Ref { .. } => v2::Parameter::default(),
+    }
+}
+
+fn scheme_name(scheme: &v2::Scheme) -> &'static str {
+    match scheme {
+        v2::Scheme::Http => "http",
+        v2::Scheme::Https => "https",
+        v2::Scheme::Ws => "ws",
+        v2::Scheme::Wss => "wss",
+    }
+}
+
+/// Returns the JSON media type if there is one, or else the first.
+fn preferred_media_type(
+    content: &BTreeMap<String, v3_0::MediaType>,
+) -> Option<(&String, &v3_0::MediaType)> {
+    content
+        .iter()
+        .find(|(media_type, _)| *media_type == "application/json" || media_type.ends_with("+json"))
+        .or_else(|| content.iter().next())
+}
+
+/// Returns whether the media type is sent as form parameters. The transpiler names URL-encoded
+/// bodies `application/form-urlencoded`, so that is accepted alongside the registered name.
+fn is_form_media_type(media_type: &str) -> bool {
+    matches!(
+        media_type,
+        "application/x-www-form-urlencoded" | "application/form-urlencoded" | "multipart/form-data"
+    )
+}
+
+/// Escapes a key for use in a JSON pointer.
+fn pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn non_empty<T>(values: Vec<T>) -> Option<Vec<T>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
+
+fn non_empty_map<K, V>(values: BTreeMap<K, V>) -> Option<BTreeMap<K, V>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
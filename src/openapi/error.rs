@@ -19,6 +19,8 @@ pub enum Error {
     SemVerError(SemVerError),
     #[error("Unsupported spec file version ({0})")]
     UnsupportedSpecFileVersion(Version),
+    #[error("{0} documents cannot be converted to {1}")]
+    UnsupportedConversion(&'static str, &'static str),
 }
 
 impl From<IoError> for Error {
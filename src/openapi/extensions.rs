@@ -0,0 +1,20 @@
+//! Support for [specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions),
+//! the `x-` prefixed fields allowed on most objects of every version of the specification.
+
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+
+/// Deserializes the `x-` prefixed fields left over by a flattened struct, ignoring any other
+/// unknown fields.
+pub(crate) fn deserialize<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, serde_json::Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let fields = BTreeMap::<String, serde_json::Value>::deserialize(deserializer)?;
+    Ok(fields
+        .into_iter()
+        .filter(|(key, _)| key.starts_with("x-"))
+        .collect())
+}
@@ -14,10 +14,14 @@
 //!
 use std::{fs::File, io::Read, path::Path, result::Result as StdResult};
 
+mod convert;
 pub mod error;
+mod extensions;
 pub mod v2;
 pub mod v3_0;
+pub mod v3_1;
 
+pub use convert::Conversion;
 pub use error::Error;
 
 const MINIMUM_OPENAPI30_VERSION: &str = ">= 3.0";
@@ -27,6 +31,7 @@ pub type Result<T> = StdResult<T, Error>;
 /// Supported versions of the OpenApi.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum OpenApi {
     /// Version 2.0 of the OpenApi specification.
     ///
@@ -35,6 +40,17 @@ pub enum OpenApi {
     /// for more information.
     V2(v2::Spec),
 
+    /// Version 3.1.0 of the OpenApi specification.
+    ///
+    /// Refer to the official
+    /// [specification](https://spec.openapis.org/oas/v3.1.0)
+    /// for more information.
+    ///
+    /// This is declared before version 3.0 so that documents are matched on their `openapi`
+    /// version before falling back to the more lenient 3.0 model.
+    #[allow(non_camel_case_types)]
+    V3_1(Box<v3_1::Spec>),
+
     /// Version 3.0.1 of the OpenApi specification.
     ///
     /// Refer to the official
@@ -101,6 +117,7 @@ mod tests {
     /// paths, comparing the result.
     /// 1. File -> `String` -> `serde_yaml::Value` -> `serde_json::Value` -> `String`
     /// 2. File -> `Spec` -> `serde_json::Value` -> `String`
+    ///
     /// Both conversion of `serde_json::Value` -> `String` are done
     /// using `serde_json::to_string_pretty`.
     /// Since the first conversion is independant of the current crate (and only
@@ -116,7 +133,7 @@ mod tests {
         //     File -> `String` -> `serde_yaml::Value` -> `serde_json::Value` -> `String`
 
         // Read the original file to string
-        let spec_yaml_str = read_to_string(&input_file)
+        let spec_yaml_str = read_to_string(input_file)
             .unwrap_or_else(|e| panic!("failed to read contents of {:?}: {}", input_file, e));
         // Convert YAML string to JSON string
         let spec_json_str = convert_yaml_str_to_json(&spec_yaml_str);
@@ -125,7 +142,7 @@ mod tests {
         //     File -> `Spec` -> `serde_json::Value` -> `String`
 
         // Parse the input file
-        let parsed_spec = from_path(&input_file).unwrap();
+        let parsed_spec = from_path(input_file).unwrap();
         // Convert to serde_json::Value
         let parsed_spec_json = serde_json::to_value(parsed_spec).unwrap();
         // Convert to a JSON string
@@ -211,6 +228,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn can_deserialize_and_reserialize_v3_1() {
+        let save_path_base: std::path::PathBuf =
+            ["target", "tests", "can_deserialize_and_reserialize_v3_1"]
+                .iter()
+                .collect();
+
+        for entry in fs::read_dir("src/openapi/data/v3.1").unwrap() {
+            let path = entry.unwrap().path();
+
+            println!("Testing if {:?} is deserializable", path);
+
+            match from_path(&path).unwrap() {
+                OpenApi::V3_1(_) => {}
+                _ => panic!("{:?} was not deserialized as OpenAPI 3.1", path),
+            }
+            let (api_filename, parsed_spec_json_str, spec_json_str) =
+                compare_spec_through_json(&path, &save_path_base);
+
+            assert_eq!(
+                parsed_spec_json_str.lines().collect::<Vec<_>>(),
+                spec_json_str.lines().collect::<Vec<_>>(),
+                "contents did not match for api {}",
+                api_filename
+            );
+        }
+    }
+
     #[test]
     fn can_deserialize_one_of_v3() {
         let openapi = from_path("src/openapi/data/v3.0/petstore-expanded.yaml").unwrap();
@@ -227,4 +272,218 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn can_convert_v3_to_v3_1() {
+        for entry in fs::read_dir("src/openapi/data/v3.0").unwrap() {
+            let path = entry.unwrap().path();
+            println!("Testing if {:?} converts to v3.1", path);
+            let spec = match from_path(&path).unwrap() {
+                OpenApi::V3_0(spec) => spec.to_v3_1().unwrap(),
+                _ => panic!("{:?} is not an OpenAPI 3.0 document", path),
+            };
+            let json = serde_json::to_string(&OpenApi::V3_1(Box::new(spec))).unwrap();
+            match from_reader(json.as_bytes()).unwrap() {
+                OpenApi::V3_1(spec) => assert_eq!(spec.openapi, "3.1.0"),
+                _ => panic!("{:?} did not convert to v3.1", path),
+            }
+        }
+    }
+
+    #[test]
+    fn can_convert_schema_keywords_to_v3_1() {
+        let spec = serde_yaml::from_str::<v3_0::Spec>(
+            r#"
+openapi: 3.0.3
+info:
+  title: Schema keywords
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Reading:
+      type: object
+      properties:
+        value:
+          type: number
+          nullable: true
+          minimum: 0
+          exclusiveMinimum: true
+          example: 21.5
+        unit:
+          type: string
+          enum: [celsius, fahrenheit]
+          nullable: true
+        note:
+          nullable: true
+          example: null
+"#,
+        )
+        .unwrap()
+        .to_v3_1()
+        .unwrap();
+        let schemas = spec.components.unwrap().schemas.unwrap();
+        let properties = schemas["Reading"].properties.as_ref().unwrap();
+
+        let value = &properties["value"];
+        assert_eq!(
+            value.schema_type,
+            Some(v3_1::SchemaType::Multiple(vec![
+                "number".to_string(),
+                "null".to_string()
+            ]))
+        );
+        assert_eq!(value.exclusive_minimum, Some(0.into()));
+        assert_eq!(value.minimum, None);
+        assert_eq!(value.examples, Some(vec![serde_json::json!(21.5)]));
+
+        let note = &properties["note"];
+        assert_eq!(
+            note.schema_type,
+            Some(v3_1::SchemaType::Single("null".to_string()))
+        );
+
+        let unit = &properties["unit"];
+        assert_eq!(
+            unit.enum_values,
+            Some(vec![
+                serde_json::json!("celsius"),
+                serde_json::json!("fahrenheit"),
+                serde_json::Value::Null
+            ])
+        );
+    }
+
+    #[test]
+    fn cannot_convert_v3_1_to_earlier_versions() {
+        let spec = from_path("src/openapi/data/v3.1/webhook-example.yaml").unwrap();
+        assert_eq!(
+            spec.to_v2().unwrap_err().to_string(),
+            "OpenAPI 3.1 documents cannot be converted to Swagger 2.0"
+        );
+        assert_eq!(
+            spec.to_v3().unwrap_err().to_string(),
+            "OpenAPI 3.1 documents cannot be converted to OpenAPI 3.0"
+        );
+    }
+
+    #[test]
+    fn can_convert_v3_to_v2() {
+        for entry in fs::read_dir("src/openapi/data/v3.0").unwrap() {
+            let path = entry.unwrap().path();
+            println!("Testing if {:?} converts to v2", path);
+            let conversion = from_path(&path).unwrap().to_v2().unwrap();
+            let json = serde_json::to_string(&OpenApi::V2(conversion.spec)).unwrap();
+            match from_reader(json.as_bytes()).unwrap() {
+                OpenApi::V2(spec) => assert_eq!(spec.swagger, "2.0"),
+                _ => panic!("{:?} did not convert to v2", path),
+            }
+        }
+    }
+
+    #[test]
+    fn can_convert_petstore_to_v2() {
+        let conversion = from_path("src/openapi/data/v3.0/petstore.yaml")
+            .unwrap()
+            .to_v2()
+            .unwrap();
+        let spec = conversion.spec;
+        assert_eq!(spec.host.as_deref(), Some("petstore.swagger.io"));
+        assert_eq!(spec.base_path.as_deref(), Some("/v1"));
+        assert_eq!(spec.schemes, Some(vec![v2::Scheme::Http]));
+        assert!(spec.definitions.unwrap().contains_key("Pets"));
+        let list_pets = spec.paths["/pets"].get.as_ref().unwrap();
+        assert_eq!(
+            list_pets.responses["200"]
+                .schema
+                .as_ref()
+                .unwrap()
+                .ref_path
+                .as_deref(),
+            Some("#/definitions/Pets")
+        );
+        assert!(conversion.warnings.is_empty());
+
+        let conversion = from_path("src/openapi/data/v3.0/callback-example.yaml")
+            .unwrap()
+            .to_v2()
+            .unwrap();
+        assert!(conversion.warnings.contains(
+            &"#/paths/~1streams/post/callbacks: callbacks are not supported".to_string()
+        ));
+    }
+
+    #[test]
+    fn can_convert_v2_to_v3() {
+        for entry in fs::read_dir("src/openapi/data/v2").unwrap() {
+            let path = entry.unwrap().path();
+            println!("Testing if {:?} converts to v3", path);
+            let conversion = from_path(&path).unwrap().to_v3().unwrap();
+            let json = serde_json::to_string(&OpenApi::V3_0(Box::new(conversion.spec))).unwrap();
+            match from_reader(json.as_bytes()).unwrap() {
+                OpenApi::V3_0(spec) => assert_eq!(spec.openapi, "3.0.3"),
+                _ => panic!("{:?} did not convert to v3", path),
+            }
+        }
+    }
+
+    #[test]
+    fn can_convert_uber_to_v3() {
+        let conversion = from_path("src/openapi/data/v2/uber.yaml")
+            .unwrap()
+            .to_v3()
+            .unwrap();
+        let spec = conversion.spec;
+        let servers = spec.servers.unwrap();
+        assert_eq!(servers[0].url, "https://api.uber.com/v1");
+        let components = spec.components.unwrap();
+        assert!(components.schemas.unwrap().contains_key("Product"));
+        assert!(matches!(
+            components.security_schemes.unwrap()["apikey"],
+            v3_0::ObjectOrReference::Object(v3_0::SecurityScheme::ApiKey { .. })
+        ));
+        let products = spec.paths["/products"].get.as_ref().unwrap();
+        let content = products.responses["default"].content.as_ref().unwrap();
+        assert_eq!(
+            content["application/json"].schema,
+            Some(v3_0::ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/Error".to_string()
+            })
+        );
+        assert!(conversion.warnings.is_empty());
+    }
+
+    #[test]
+    fn can_convert_petstore_to_v3() {
+        let conversion = from_path("src/openapi/data/v2/petstore-simple.yaml")
+            .unwrap()
+            .to_v3()
+            .unwrap();
+        let spec = conversion.spec;
+        let pets = &spec.paths["/pets"];
+        let tags = match &pets.get.as_ref().unwrap().parameters.as_ref().unwrap()[0] {
+            v3_0::ObjectOrReference::Object(parameter) => parameter.clone(),
+            _ => panic!("the tags parameter is not inline"),
+        };
+        assert_eq!(tags.style, Some(v3_0::ParameterStyle::Form));
+        assert_eq!(tags.explode, Some(false));
+        let body = match pets.post.as_ref().unwrap().request_body.as_ref().unwrap() {
+            v3_0::ObjectOrReference::Object(body) => body.clone(),
+            _ => panic!("the request body is not inline"),
+        };
+        assert_eq!(body.required, Some(true));
+        assert_eq!(
+            body.content["application/json"].schema,
+            Some(v3_0::ObjectOrReference::Ref {
+                ref_path: "#/components/schemas/NewPet".to_string()
+            })
+        );
+        assert!(conversion.warnings.is_empty());
+
+        let conversion = from_path("src/openapi/data/v2/rocks.yaml")
+            .unwrap()
+            .to_v3()
+            .unwrap();
+        assert!(conversion.spec.components.unwrap().parameters.is_some());
+    }
 }
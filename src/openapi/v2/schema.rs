@@ -1,3 +1,4 @@
+use super::super::extensions;
 use std::collections::BTreeMap;
 
 // http://json.schemastore.org/swagger-2.0
@@ -58,6 +59,9 @@ pub struct Spec {
     pub security: Option<Vec<BTreeMap<String, Vec<String>>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub external_docs: Option<Vec<ExternalDoc>>,
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#vendorExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -97,6 +101,9 @@ pub struct Info {
     pub license: Option<License>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#vendorExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
@@ -124,7 +131,6 @@ pub struct License {
     pub url: Option<String>,
 }
 
-/// todo support x-* properties
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct PathItem {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -143,6 +149,9 @@ pub struct PathItem {
     pub head: Option<Operation>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Vec<ParameterOrRef>>,
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#vendorExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#operation-object
@@ -168,6 +177,11 @@ pub struct Operation {
     pub parameters: Option<Vec<ParameterOrRef>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub security: Option<Vec<SecurityRequirement>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#vendorExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#securityRequirementObject
@@ -195,20 +209,75 @@ pub struct Parameter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Schema>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    default: Option<serde_json::Value>,
+    pub collection_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "enum")]
+    pub enum_values: Option<Vec<serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_empty_value: Option<bool>,
+    /// An example value, as the widely supported `x-example` extension.
+    #[serde(rename = "x-example", skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+}
+
+impl From<Parameter> for ParameterOrRef {
+    fn from(parameter: Parameter) -> Self {
+        ParameterOrRef::Parameter {
+            name: parameter.name,
+            location: parameter.location,
+            required: parameter.required,
+            schema: parameter.schema,
+            unique_items: parameter.unique_items,
+            param_type: parameter.param_type,
+            format: parameter.format,
+            description: parameter.description,
+            collection_format: parameter.collection_format,
+            default: parameter.default,
+            enum_values: parameter.enum_values,
+            allow_empty_value: parameter.allow_empty_value,
+            items: parameter.items,
+            additional_properties: None,
+            example: parameter.example,
+        }
+    }
 }
 
+/// https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#response-object
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Response {
     pub description: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, Header>>,
+    /// Example response bodies, keyed by MIME type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<BTreeMap<String, serde_json::Value>>,
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#vendorExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#header-object
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Header {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub header_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Schema>,
 }
 
 // todo: support x-* fields
 /// https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#parameter-object
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum ParameterOrRef {
     /// both bodyParameter and nonBodyParameter in one for now
     Parameter {
@@ -248,9 +317,12 @@ pub enum ParameterOrRef {
         // pattern ??
         // maxItems ??
         // minItems ??
-        // enum ??
         // multipleOf ??
-        // allowEmptyValue ( for query / body params )
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(rename = "enum")]
+        enum_values: Option<Vec<serde_json::Value>>,
+        #[serde(rename = "allowEmptyValue", skip_serializing_if = "Option::is_none")]
+        allow_empty_value: Option<bool>,
         #[serde(skip_serializing_if = "Option::is_none")]
         items: Option<Schema>,
         #[serde(
@@ -258,6 +330,9 @@ pub enum ParameterOrRef {
             skip_serializing_if = "Option::is_none"
         )]
         additional_properties: Option<Schema>,
+        /// An example value, as the widely supported `x-example` extension.
+        #[serde(rename = "x-example", skip_serializing_if = "Option::is_none")]
+        example: Option<serde_json::Value>,
     },
     Ref {
         #[serde(rename = "$ref")]
@@ -278,7 +353,12 @@ pub enum Security {
     #[serde(rename = "oauth2")]
     Oauth2 {
         flow: Flow,
-        #[serde(rename = "authorizationUrl")]
+        /// Required by the `implicit` and `accessCode` flows only.
+        #[serde(
+            rename = "authorizationUrl",
+            default,
+            skip_serializing_if = "String::is_empty"
+        )]
         authorization_url: String,
         #[serde(rename = "tokenUrl")]
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -323,7 +403,7 @@ pub struct Schema {
     pub format: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "enum")]
-    pub enum_values: Option<Vec<String>>,
+    pub enum_values: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -351,7 +431,7 @@ mod tests {
     fn security_api_deserializes() {
         let json = r#"{"type":"apiKey", "name":"foo", "in": "query"}"#;
         assert_eq!(
-            serde_yaml::from_str::<Security>(&json).unwrap(),
+            serde_yaml::from_str::<Security>(json).unwrap(),
             Security::ApiKey {
                 name: "foo".into(),
                 location: "query".into(),
@@ -378,7 +458,7 @@ mod tests {
     fn security_basic_deserializes() {
         let json = r#"{"type":"basic"}"#;
         assert_eq!(
-            serde_yaml::from_str::<Security>(&json).unwrap(),
+            serde_yaml::from_str::<Security>(json).unwrap(),
             Security::Basic { description: None }
         );
     }
@@ -398,12 +478,12 @@ mod tests {
         let mut scopes = BTreeMap::new();
         scopes.insert("foo".into(), "bar".into());
         assert_eq!(
-            serde_yaml::from_str::<Security>(&json).unwrap(),
+            serde_yaml::from_str::<Security>(json).unwrap(),
             Security::Oauth2 {
                 flow: Flow::Implicit,
                 authorization_url: "foo/bar".into(),
                 token_url: None,
-                scopes: scopes,
+                scopes,
                 description: None,
             }
         );
@@ -420,7 +500,7 @@ mod tests {
                 flow: Flow::Implicit,
                 authorization_url: "foo/bar".into(),
                 token_url: None,
-                scopes: scopes,
+                scopes,
                 description: None,
             })
             .unwrap()
@@ -431,7 +511,7 @@ mod tests {
     fn parameter_or_ref_deserializes_ref() {
         let json = r#"{"$ref":"foo/bar"}"#;
         assert_eq!(
-            serde_yaml::from_str::<ParameterOrRef>(&json).unwrap(),
+            serde_yaml::from_str::<ParameterOrRef>(json).unwrap(),
             ParameterOrRef::Ref {
                 ref_path: "foo/bar".into()
             }
@@ -1,6 +1,7 @@
 use super::schema::{
     Callback, Example, Header, Link, Parameter, RequestBody, Response, Schema, SecurityScheme,
 };
+use crate::openapi::extensions;
 use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -56,5 +57,8 @@ pub struct Components {
     /// An object to hold reusable Callback Objects.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub callbacks: Option<BTreeMap<String, ObjectOrReference<Callback>>>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
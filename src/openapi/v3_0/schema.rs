@@ -1,13 +1,15 @@
 //! Schema specification for [OpenAPI 3.0.0](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.0.md)
 
 use indexmap::IndexSet;
+use serde::{de::Error as _, Deserialize, Deserializer};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::BTreeMap,
     hash::{Hash, Hasher},
 };
 use url::Url;
 
 use super::{
+    super::extensions,
     super::Error,
     super::Result,
     super::MINIMUM_OPENAPI30_VERSION,
@@ -63,13 +65,13 @@ pub struct Spec {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Components>,
 
-    // FIXME: Implement
-    // /// A declaration of which security mechanisms can be used across the API.
-    // /// The list of  values includes alternative security requirement objects that can be used.
-    // /// Only one of the security requirement objects need to be satisfied to authorize a request.
-    // /// Individual operations can override this definition.
-    // #[serde(skip_serializing_if = "Option::is_none")]
-    // pub security: Option<SecurityRequirement>,
+    /// A declaration of which security mechanisms can be used across the API.
+    /// The list of  values includes alternative security requirement objects that can be used.
+    /// Only one of the security requirement objects need to be satisfied to authorize a request.
+    /// Individual operations can override this definition.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
     /// A list of tags used by the specification with additional metadata.
     ///The order of the tags can be used to reflect on their order by the parsing tools.
     /// Not all tags that are used by the
@@ -82,7 +84,10 @@ pub struct Spec {
     /// Additional external documentation.
     #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
     pub external_docs: Option<ExternalDoc>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// General information about the API.
@@ -110,6 +115,10 @@ pub struct Info {
     /// The license information for the exposed API.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<License>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Contact information for the exposed API.
@@ -126,7 +135,10 @@ pub struct Contact {
     // TODO: Make sure the email is a valid email
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// License information for the exposed API.
@@ -139,7 +151,10 @@ pub struct License {
     /// A URL to the license used for the API.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<Url>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// An object representing a Server.
@@ -159,6 +174,10 @@ pub struct Server {
     /// the server's URL template.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variables: Option<BTreeMap<String, ServerVariable>>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// An object representing a Server Variable for server URL template substitution.
@@ -179,6 +198,10 @@ pub struct ServerVariable {
     /// [CommonMark]: https://spec.commonmark.org/
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Describes the operations available on a single path.
@@ -248,7 +271,10 @@ pub struct PathItem {
     /// [OpenAPI Object's components/parameters](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#componentsParameters).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Vec<ObjectOrReference<Parameter>>>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Describes a single API operation on a path.
@@ -322,29 +348,33 @@ pub struct Operation {
     /// an expression, evaluated at runtime, that identifies a URL to use for the
     /// callback operation.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub callbacks: Option<BTreeMap<String, Callback>>,
+    pub callbacks: Option<BTreeMap<String, ObjectOrReference<Callback>>>,
 
     /// Declares this operation to be deprecated. Consumers SHOULD refrain from usage
     /// of the declared operation. Default value is `false`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<bool>,
 
-    // FIXME: Implement
-    // /// A declaration of which security mechanisms can be used for this operation. The list of
-    // /// values includes alternative security requirement objects that can be used. Only one
-    // /// of the security requirement objects need to be satisfied to authorize a request.
-    // /// This definition overrides any declared top-level
-    // /// [`security`](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#oasSecurity).
-    // /// To remove a top-level security declaration, an empty array can be used.
-    // pub security: Option<SecurityRequirement>,
+    /// A declaration of which security mechanisms can be used for this operation. The list of
+    /// values includes alternative security requirement objects that can be used. Only one
+    /// of the security requirement objects need to be satisfied to authorize a request.
+    /// This definition overrides any declared top-level
+    /// [`security`](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#oasSecurity).
+    /// To remove a top-level security declaration, an empty array can be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
     /// An alternative `server` array to service this operation. If an alternative `server`
     /// object is specified at the Path Item Object or Root level, it will be overridden by
     /// this value.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub servers: Option<Vec<Server>>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
-// FIXME: Verify against OpenAPI 3.0
 /// Describes a single operation parameter.
 /// A unique parameter is defined by a combination of a
 /// [name](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#parameterName)
@@ -355,53 +385,80 @@ pub struct Operation {
 pub struct Parameter {
     /// The name of the parameter.
     pub name: String,
-    /// values depend on parameter type
-    /// may be `header`, `query`, 'path`, `formData`
+
+    /// The location of the parameter: `query`, `header`, `path` or `cookie`.
     #[serde(rename = "in")]
     pub location: String,
+
+    /// Determines whether this parameter is mandatory. If the parameter location is `path`,
+    /// this property is REQUIRED and its value MUST be `true`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
+
+    /// The schema defining the type used for the parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<Schema>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "uniqueItems")]
-    pub unique_items: Option<bool>,
-    /// string, number, boolean, integer, array, file ( only for formData )
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "type")]
-    pub param_type: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub format: Option<String>,
-    /// A brief description of the parameter. This could contain examples
-    /// of use.  GitHub Flavored Markdown is allowed.
+
+    /// A brief description of the parameter. This could contain examples of use.
+    /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    // collectionFormat: ???
-    // default: ???
-    // maximum ?
-    // exclusiveMaximum ??
-    // minimum ??
-    // exclusiveMinimum ??
-    // maxLength ??
-    // minLength ??
-    // pattern ??
-    // maxItems ??
-    // minItems ??
-    // enum ??
-    // multipleOf ??
-    // allowEmptyValue ( for query / body params )
+
+    /// Specifies that a parameter is deprecated and SHOULD be transitioned out of usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    /// Sets the ability to pass empty-valued parameters. This is valid only for `query`
+    /// parameters and allows sending a parameter with an empty value.
+    #[serde(rename = "allowEmptyValue", skip_serializing_if = "Option::is_none")]
+    pub allow_empty_value: Option<bool>,
+
     /// Describes how the parameter value will be serialized depending on the type of the parameter
     /// value. Default values (based on value of in): for `query` - `form`; for `path` - `simple`; for
     /// `header` - `simple`; for cookie - `form`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    style: Option<ParameterStyle>,
+    pub style: Option<ParameterStyle>,
+
+    /// When this is true, parameter values of type `array` or `object` generate separate
+    /// parameters for each value of the array or key-value pair of the map. When `style` is
+    /// `form`, the default value is `true`; for all other styles it is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+
+    /// Determines whether the parameter value SHOULD allow reserved characters, as defined by
+    /// [RFC3986](https://tools.ietf.org/html/rfc3986#section-2.2), to be included without
+    /// percent-encoding. This property only applies to `query` parameters.
+    #[serde(rename = "allowReserved", skip_serializing_if = "Option::is_none")]
+    pub allow_reserved: Option<bool>,
+
+    /// Example or examples of the parameter's potential value.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub examples: Option<MediaTypeExample>,
+
+    /// A map containing the representations for the parameter. The key is the media type and
+    /// the value describes it. The map MUST only contain one entry, and is mutually exclusive
+    /// with `schema`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<BTreeMap<String, MediaType>>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
+/// Describes how a parameter value is serialized.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#style-values>.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
-enum ParameterStyle {
+pub enum ParameterStyle {
+    Matrix,
+    Label,
     Form,
     Simple,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
 }
 
 // FIXME: Verify against OpenAPI 3.0
@@ -435,7 +492,7 @@ pub struct Schema {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "enum")]
-    pub enum_values: Option<Vec<String>>,
+    pub enum_values: Option<Vec<serde_json::Value>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<Vec<String>>,
@@ -449,10 +506,16 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none", rename = "readOnly")]
     pub read_only: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none", rename = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    /// Specifies that a schema is deprecated and SHOULD be transitioned out of usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nullable: Option<bool>,
 
-    // FIXME: Why can this be a "boolean" (as per the spec)? It doesn't make sense. Here it's not.
     /// Value can be boolean or object. Inline or referenced schema MUST be of a
     /// [Schema Object](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#schemaObject)
     /// and not a standard JSON Schema.
@@ -462,7 +525,7 @@ pub struct Schema {
         skip_serializing_if = "Option::is_none",
         rename = "additionalProperties"
     )]
-    pub additional_properties: Option<ObjectOrReference<Box<Schema>>>,
+    pub additional_properties: Option<AdditionalProperties>,
 
     /// A free-form property to include an example of an instance for this schema.
     /// To represent examples that cannot be naturally represented in JSON or YAML,
@@ -477,24 +540,6 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
 
-    // The following properties are taken directly from the JSON Schema definition and
-    // follow the same specifications:
-    // multipleOf
-    // maximum
-    // exclusiveMaximum
-    // minimum
-    // exclusiveMinimum
-    // maxLength
-    // minLength
-    // pattern (This string SHOULD be a valid regular expression, according to the ECMA 262 regular expression dialect)
-    // maxItems
-    // minItems
-    // uniqueItems
-    // maxProperties
-    // minProperties
-    // required
-    // enum
-
     // The following properties are taken from the JSON Schema definition but their
     // definitions were adjusted to the OpenAPI Specification.
     // - type - Value MUST be a string. Multiple types via an array are not supported.
@@ -515,9 +560,21 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<serde_json::Value>,
 
+    #[serde(rename = "multipleOf", skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<serde_json::Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<serde_json::Value>,
+
+    #[serde(rename = "exclusiveMaximum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum: Option<serde_json::Value>,
 
+    #[serde(rename = "exclusiveMinimum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<bool>,
+
     /// Inline or referenced schema MUST be of a [Schema Object](#schemaObject) and not a standard
     /// JSON Schema.
     /// [allOf](https://swagger.io/docs/specification/data-models/oneof-anyof-allof-not/#allof)
@@ -540,7 +597,19 @@ pub struct Schema {
     /// JSON Schema.
     /// [not](https://swagger.io/docs/specification/data-models/oneof-anyof-allof-not/#not)
     #[serde(rename = "not", skip_serializing_if = "Option::is_none")]
-    pub not: Option<Vec<ObjectOrReference<Schema>>>,
+    pub not: Option<Box<ObjectOrReference<Schema>>>,
+
+    /// Adds support for polymorphism. The discriminator is an object name that is used to
+    /// differentiate between other schemas which may satisfy the payload description.
+    ///
+    /// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#discriminatorObject>.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Discriminator>,
+
+    /// This MAY be used only on properties schemas. It has no effect on root schemas. Adds
+    /// additional metadata to describe the XML representation of this property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xml: Option<Xml>,
 
     #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
     pub max_length: Option<u32>,
@@ -548,9 +617,91 @@ pub struct Schema {
     #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
     pub min_length: Option<u32>,
 
-    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.2.md#specificationExtensions)
-    #[serde(flatten)]
-    pub extensions: HashMap<String, String>,
+    /// This string SHOULD be a valid regular expression, according to the ECMA 262 regular
+    /// expression dialect.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    #[serde(rename = "maxItems", skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<u32>,
+
+    #[serde(rename = "minItems", skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<u32>,
+
+    #[serde(rename = "uniqueItems", skip_serializing_if = "Option::is_none")]
+    pub unique_items: Option<bool>,
+
+    #[serde(rename = "maxProperties", skip_serializing_if = "Option::is_none")]
+    pub max_properties: Option<u32>,
+
+    #[serde(rename = "minProperties", skip_serializing_if = "Option::is_none")]
+    pub min_properties: Option<u32>,
+
+    /// Additional external documentation for this schema.
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDoc>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// The value of `additionalProperties`: either whether additional properties are allowed, or
+/// the schema they must match.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum AdditionalProperties {
+    Bool(bool),
+    Schema(ObjectOrReference<Box<Schema>>),
+}
+
+/// When request bodies or response payloads may be one of a number of different schemas, a
+/// `discriminator` object can be used to aid in serialization, deserialization, and
+/// validation.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#discriminatorObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Discriminator {
+    /// The name of the property in the payload that will hold the discriminator value.
+    #[serde(rename = "propertyName")]
+    pub property_name: String,
+
+    /// An object to hold mappings between payload values and schema names or references.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mapping: Option<BTreeMap<String, String>>,
+}
+
+/// A metadata object that allows for more fine-tuned XML model definitions.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#xmlObject>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Xml {
+    /// Replaces the name of the element/attribute used for the described schema property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The URI of the namespace definition.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// The prefix to be used for the name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+
+    /// Declares whether the property definition translates to an attribute instead of an
+    /// element. Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<bool>,
+
+    /// MAY be used only for an array definition. Signifies whether the array is wrapped
+    /// (for example, `<books><book/><book/></books>`) or unwrapped (`<book/><book/>`).
+    /// Default value is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrapped: Option<bool>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Describes a single response from an API Operation, including design-time, static `links`
@@ -582,7 +733,10 @@ pub struct Response {
     /// [Component Objects](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#componentsObject).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub links: Option<BTreeMap<String, ObjectOrReference<Link>>>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// The Header Object follows the structure of the
@@ -596,39 +750,56 @@ pub struct Response {
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#headerObject>.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Header {
-    // FIXME: Is the third change properly implemented?
-    // FIXME: Merge `ObjectOrReference<Header>::Reference` and `ParameterOrRef::Reference`
+    /// Determines whether this header is mandatory.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
+
+    /// The schema defining the type used for the header.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<Schema>,
+
+    /// A brief description of the header. This could contain examples of use.
+    /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "uniqueItems")]
-    pub unique_items: Option<bool>,
-    /// string, number, boolean, integer, array, file ( only for formData )
+    pub description: Option<String>,
+
+    /// Specifies that a header is deprecated and SHOULD be transitioned out of usage.
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "type")]
-    pub param_type: Option<String>,
+    pub deprecated: Option<bool>,
+
+    /// Kept for parity with the Parameter Object. It only applies to `query` parameters, so it
+    /// has no effect on a header.
+    #[serde(rename = "allowEmptyValue", skip_serializing_if = "Option::is_none")]
+    pub allow_empty_value: Option<bool>,
+
+    /// Describes how the header value will be serialized. Headers use the `simple` style.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub format: Option<String>,
-    /// A brief description of the parameter. This could contain examples
-    /// of use.  GitHub Flavored Markdown is allowed.
+    pub style: Option<ParameterStyle>,
+
+    /// When this is true, an `object` header value is serialized as comma-separated
+    /// `key=value` pairs instead of alternating keys and values. Arrays are comma-separated
+    /// either way. Defaults to `false`.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
-    // collectionFormat: ???
-    // default: ???
-    // maximum ?
-    // exclusiveMaximum ??
-    // minimum ??
-    // exclusiveMinimum ??
-    // maxLength ??
-    // minLength ??
-    // pattern ??
-    // maxItems ??
-    // minItems ??
-    // enum ??
-    // multipleOf ??
-    // allowEmptyValue ( for query / body params )
+    pub explode: Option<bool>,
+
+    /// Kept for parity with the Parameter Object. It only applies to `query` parameters, so it
+    /// has no effect on a header.
+    #[serde(rename = "allowReserved", skip_serializing_if = "Option::is_none")]
+    pub allow_reserved: Option<bool>,
+
+    /// Example or examples of the header's potential value.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub examples: Option<MediaTypeExample>,
+
+    /// A map containing the representations for the header. The key is the media type and
+    /// the value describes it. The map MUST only contain one entry, and is mutually exclusive
+    /// with `schema`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<BTreeMap<String, MediaType>>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Describes a single request body.
@@ -649,6 +820,10 @@ pub struct RequestBody {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// The Link object represents a possible design-time link for a response.
@@ -679,24 +854,22 @@ pub enum Link {
         #[serde(rename = "operationRef")]
         operation_ref: String,
 
-        // FIXME: Implement
-        // /// A map representing parameters to pass to an operation as specified with `operationId`
-        // /// or identified via `operationRef`. The key is the parameter name to be used, whereas
-        // /// the value can be a constant or an expression to be evaluated and passed to the
-        // /// linked operation. The parameter name can be qualified using the
-        // /// [parameter location](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#parameterIn)
-        // /// `[{in}.]{name}` for operations that use the same parameter name in different
-        // /// locations (e.g. path.id).
-        // parameters: BTreeMap<String, Any | {expression}>,
+        /// A map representing parameters to pass to an operation as specified with `operationId`
+        /// or identified via `operationRef`. The key is the parameter name to be used, whereas
+        /// the value can be a constant or an expression to be evaluated and passed to the
+        /// linked operation. The parameter name can be qualified using the
+        /// [parameter location](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#parameterIn)
+        /// `[{in}.]{name}` for operations that use the same parameter name in different
+        /// locations (e.g. path.id).
         #[serde(skip_serializing_if = "Option::is_none")]
-        parameters: Option<BTreeMap<String, String>>,
-
-        // FIXME: Implement
-        // /// A literal value or
-        // /// [{expression}](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#runtimeExpression)
-        // /// to use as a request body when calling the target operation.
-        // #[serde(rename = "requestBody")]
-        // request_body: Any | {expression}
+        parameters: Option<BTreeMap<String, serde_json::Value>>,
+
+        /// A literal value or
+        /// [{expression}](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#runtimeExpression)
+        /// to use as a request body when calling the target operation.
+        #[serde(rename = "requestBody", skip_serializing_if = "Option::is_none")]
+        request_body: Option<serde_json::Value>,
+
         /// A description of the link. [CommonMark syntax](http://spec.commonmark.org/) MAY be
         /// used for rich text representation.
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -705,7 +878,10 @@ pub enum Link {
         /// A server object to be used by the target operation.
         #[serde(skip_serializing_if = "Option::is_none")]
         server: Option<Server>,
-        // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtension
+
+        /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
     },
     /// The name of an _existing_, resolvable OAS operation, as defined with a unique
     /// `operationId`. This field is mutually exclusive of the `operationRef` field.
@@ -713,24 +889,22 @@ pub enum Link {
         #[serde(rename = "operationId")]
         operation_id: String,
 
-        // FIXME: Implement
-        // /// A map representing parameters to pass to an operation as specified with `operationId`
-        // /// or identified via `operationRef`. The key is the parameter name to be used, whereas
-        // /// the value can be a constant or an expression to be evaluated and passed to the
-        // /// linked operation. The parameter name can be qualified using the
-        // /// [parameter location](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#parameterIn)
-        // /// `[{in}.]{name}` for operations that use the same parameter name in different
-        // /// locations (e.g. path.id).
-        // parameters: BTreeMap<String, Any | {expression}>,
+        /// A map representing parameters to pass to an operation as specified with `operationId`
+        /// or identified via `operationRef`. The key is the parameter name to be used, whereas
+        /// the value can be a constant or an expression to be evaluated and passed to the
+        /// linked operation. The parameter name can be qualified using the
+        /// [parameter location](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#parameterIn)
+        /// `[{in}.]{name}` for operations that use the same parameter name in different
+        /// locations (e.g. path.id).
         #[serde(skip_serializing_if = "Option::is_none")]
-        parameters: Option<BTreeMap<String, String>>,
-
-        // FIXME: Implement
-        // /// A literal value or
-        // /// [{expression}](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#runtimeExpression)
-        // /// to use as a request body when calling the target operation.
-        // #[serde(rename = "requestBody")]
-        // request_body: Any | {expression}
+        parameters: Option<BTreeMap<String, serde_json::Value>>,
+
+        /// A literal value or
+        /// [{expression}](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#runtimeExpression)
+        /// to use as a request body when calling the target operation.
+        #[serde(rename = "requestBody", skip_serializing_if = "Option::is_none")]
+        request_body: Option<serde_json::Value>,
+
         /// A description of the link. [CommonMark syntax](http://spec.commonmark.org/) MAY be
         /// used for rich text representation.
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -739,7 +913,10 @@ pub enum Link {
         /// A server object to be used by the target operation.
         #[serde(skip_serializing_if = "Option::is_none")]
         server: Option<Server>,
-        // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtension
+
+        /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
     },
 }
 
@@ -762,6 +939,10 @@ pub struct MediaType {
     /// or `application/x-www-form-urlencoded`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<BTreeMap<String, Encoding>>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -827,6 +1008,10 @@ pub struct Encoding {
     /// not `application/x-www-form-urlencoded`.
     #[serde(skip_serializing_if = "Option::is_none", rename = "allowReserved")]
     pub allow_reserved: Option<bool>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#exampleObject>.
@@ -840,20 +1025,22 @@ pub struct Example {
     /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    // FIXME: Implement (merge with externalValue as enum)
+
     /// Embedded literal example. The `value` field and `externalValue` field are mutually
     /// exclusive. To represent examples of media types that cannot naturally represented
     /// in JSON or YAML, use a string value to contain the example, escaping where necessary.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<serde_json::Value>,
-    // FIXME: Implement (merge with value as enum)
-    // /// A URL that points to the literal example. This provides the capability to reference
-    // /// examples that cannot easily be included in JSON or YAML documents. The `value` field
-    // /// and `externalValue` field are mutually exclusive.
-    // #[serde(skip_serializing_if = "Option::is_none")]
-    // pub externalValue: Option<String>,
 
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+    /// A URL that points to the literal example. This provides the capability to reference
+    /// examples that cannot easily be included in JSON or YAML documents. The `value` field
+    /// and `externalValue` field are mutually exclusive.
+    #[serde(rename = "externalValue", skip_serializing_if = "Option::is_none")]
+    pub external_value: Option<String>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Defines a security scheme that can be used by the operations. Supported schemes are
@@ -868,29 +1055,58 @@ pub struct Example {
 pub enum SecurityScheme {
     #[serde(rename = "apiKey")]
     ApiKey {
+        /// The name of the header, query or cookie parameter to be used.
         name: String,
+        /// The location of the API key: `query`, `header` or `cookie`.
         #[serde(rename = "in")]
         location: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
     },
     #[serde(rename = "http")]
     Http {
+        /// The name of the HTTP Authorization scheme, e.g. `basic` or `bearer`.
         scheme: String,
-        #[serde(rename = "bearerFormat")]
-        bearer_format: String,
+        /// A hint to the client to identify how the bearer token is formatted.
+        #[serde(rename = "bearerFormat", skip_serializing_if = "Option::is_none")]
+        bearer_format: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
     },
     #[serde(rename = "oauth2")]
-    OAuth2 { flows: Box<Flows> },
+    OAuth2 {
+        flows: Box<Flows>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
+    },
     #[serde(rename = "openIdConnect")]
     OpenIdConnect {
         #[serde(rename = "openIdConnectUrl")]
         open_id_connect_url: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
     },
 }
 
+/// Lists the required security schemes to execute an operation. Each name MUST correspond to a
+/// security scheme declared in `components.securitySchemes`. For `oauth2` and `openIdConnect`
+/// schemes the value is the list of scope names required; for other schemes it MUST be empty.
+///
+/// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#securityRequirementObject>.
+pub type SecurityRequirement = BTreeMap<String, Vec<String>>;
+
 /// Allows configuration of the supported OAuth Flows.
 /// See [link]
 /// [link][https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#oauth-flows-object]
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Flows {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -901,6 +1117,10 @@ pub struct Flows {
     pub client_credentials: Option<ClientCredentialsFlow>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authorization_code: Option<AuthorizationCodeFlow>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Configuration details for a implicit OAuth Flow
@@ -913,6 +1133,10 @@ pub struct ImplicitFlow {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_url: Option<Url>,
     pub scopes: BTreeMap<String, String>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Configuration details for a password OAuth Flow
@@ -921,10 +1145,14 @@ pub struct ImplicitFlow {
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct PasswordFlow {
-    token_url: Url,
+    pub token_url: Url,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_url: Option<Url>,
     pub scopes: BTreeMap<String, String>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Configuration details for a client credentials OAuth Flow
@@ -933,10 +1161,14 @@ pub struct PasswordFlow {
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ClientCredentialsFlow {
-    token_url: Url,
+    pub token_url: Url,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_url: Option<Url>,
     pub scopes: BTreeMap<String, String>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 /// Configuration details for a authorization code OAuth Flow
@@ -946,13 +1178,16 @@ pub struct ClientCredentialsFlow {
 #[serde(rename_all = "camelCase")]
 pub struct AuthorizationCodeFlow {
     pub authorization_url: Url,
-    token_url: Url,
+    pub token_url: Url,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_url: Option<Url>,
     pub scopes: BTreeMap<String, String>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
-// TODO: Implement
 /// A map of possible out-of band callbacks related to the parent operation. Each value in
 /// the map is a Path Item Object that describes a set of requests that may be initiated by
 /// the API provider and the expected responses. The key value used to identify the callback
@@ -960,18 +1195,38 @@ pub struct AuthorizationCodeFlow {
 /// callback operation.
 ///
 /// See <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#callbackObject>.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
-pub struct Callback(
-    /// A Path Item Object used to define a callback request and expected responses.
-    serde_json::Value, // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
-);
-
-// FIXME: Implement
-// /// Allows configuration of the supported OAuth Flows.
-// /// https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#oauthFlowsObject
-// #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
-// pub struct OAuthFlows {
-// }
+#[derive(Clone, Debug, Serialize, PartialEq, Default)]
+pub struct Callback {
+    /// Path Item Objects used to define the callback requests and expected responses, keyed by
+    /// a [runtime expression](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#runtimeExpression)
+    /// such as `{$request.body#/callbackUrl}`.
+    #[serde(flatten)]
+    pub paths: BTreeMap<String, PathItem>,
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten)]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+// Every field of a callback is either an extension or an expression, so the two flattened maps
+// cannot be told apart by serde and are split by prefix instead.
+impl<'de> Deserialize<'de> for Callback {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut callback = Callback::default();
+        for (key, value) in BTreeMap::<String, serde_json::Value>::deserialize(deserializer)? {
+            if key.starts_with("x-") {
+                callback.extensions.insert(key, value);
+            } else {
+                let path = serde_json::from_value(value).map_err(D::Error::custom)?;
+                callback.paths.insert(key, path);
+            }
+        }
+        Ok(callback)
+    }
+}
 
 /// Adds metadata to a single tag that is used by the
 /// [Operation Object](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#operationObject).
@@ -990,8 +1245,9 @@ pub struct Tag {
     // /// Additional external documentation for this tag.
     // #[serde(skip_serializing_if = "Option::is_none")]
     // pub external_docs: Option<Vec<ExternalDoc>>,
-
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 impl Hash for Tag {
@@ -1018,7 +1274,10 @@ pub struct ExternalDoc {
     /// [CommonMark syntax](http://spec.commonmark.org/) MAY be used for rich text representation.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    // TODO: Add "Specification Extensions" https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions}
+
+    /// [Specification extensions](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.1.md#specificationExtensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
 #[cfg(test)]
@@ -1047,9 +1306,9 @@ mod tests {
             }
           }
         }"#;
-        let obj: SecurityScheme = serde_json::from_str(&IMPLICIT_OAUTH2_SAMPLE).unwrap();
+        let obj: SecurityScheme = serde_json::from_str(IMPLICIT_OAUTH2_SAMPLE).unwrap();
         match obj {
-            SecurityScheme::OAuth2 { flows } => {
+            SecurityScheme::OAuth2 { flows, .. } => {
                 assert!(flows.implicit.is_some());
                 let implicit = flows.implicit.unwrap();
                 assert_eq!(
@@ -1072,7 +1331,17 @@ mod tests {
                 assert!(implicit.scopes.contains_key("write:pets"));
                 assert!(implicit.scopes.contains_key("read:pets"));
             }
-            _ => assert!(false, "wrong security scheme type"),
+            _ => panic!("wrong security scheme type"),
         }
     }
+
+    #[test]
+    fn test_extensions_ignore_unknown_fields() {
+        let info: Info = serde_json::from_str(
+            r#"{"title": "Pets", "version": "1.0", "x-logo": {"url": "logo.png"}, "logo": "x"}"#,
+        )
+        .unwrap();
+        assert_eq!(info.extensions.len(), 1);
+        assert_eq!(info.extensions["x-logo"]["url"], "logo.png");
+    }
 }
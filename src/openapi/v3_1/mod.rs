@@ -0,0 +1,17 @@
+//! Support for OpenApi version 3.1.0 specification.
+//!
+//! See the
+//! [specification](https://spec.openapis.org/oas/v3.1.0)
+//! for more information.
+//!
+//! Objects that are unchanged from version 3.0 and do not contain schemas are shared with the
+//! [`v3_0`](../v3_0/index.html) module.
+
+mod schema;
+
+pub use self::schema::*;
+pub use super::v3_0::{
+    AuthorizationCodeFlow, ClientCredentialsFlow, Contact, Discriminator, Example, ExternalDoc,
+    Flows, ImplicitFlow, Link, MediaTypeExample, ObjectOrReference, ParameterStyle, PasswordFlow,
+    SecurityRequirement, Server, ServerVariable, Tag, Xml,
+};
@@ -0,0 +1,632 @@
+//! Schema specification for [OpenAPI 3.1.0](https://spec.openapis.org/oas/v3.1.0)
+
+use indexmap::IndexSet;
+use serde::{de::Error as _, Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use url::Url;
+
+use super::{
+    super::extensions, Contact, Discriminator, Example, ExternalDoc, Flows, Link, MediaTypeExample,
+    ObjectOrReference, ParameterStyle, SecurityRequirement, Server, Tag, Xml,
+};
+
+/// top level document
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Spec {
+    /// The version number of the
+    /// [OpenAPI Specification](https://spec.openapis.org/oas/v3.1.0#versions) that the
+    /// document uses, which MUST be 3.1.x.
+    #[serde(deserialize_with = "deserialize_version")]
+    pub openapi: String,
+    /// Provides metadata about the API. The metadata MAY be used by tooling as required.
+    pub info: Info,
+    /// The default value for the `$schema` keyword within Schema Objects contained within this
+    /// document.
+    #[serde(rename = "jsonSchemaDialect", skip_serializing_if = "Option::is_none")]
+    pub json_schema_dialect: Option<String>,
+    /// An array of Server Objects, which provide connectivity information to a target server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+    /// The available paths and operations for the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paths: Option<BTreeMap<String, PathItem>>,
+    /// The incoming webhooks that MAY be received as part of this API and that the API consumer
+    /// MAY choose to implement, keyed by a unique name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<BTreeMap<String, PathItem>>,
+    /// An element to hold various schemas for the specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Components>,
+    /// A declaration of which security mechanisms can be used across the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// A list of tags used by the specification with additional metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<IndexSet<Tag>>,
+    /// Additional external documentation.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDoc>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+// Version 3.1 documents are also valid 3.0 documents as far as serde is concerned, so the version
+// is what tells them apart.
+fn deserialize_version<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let version = String::deserialize(deserializer)?;
+    if version.starts_with("3.1.") {
+        Ok(version)
+    } else {
+        Err(D::Error::custom(format!(
+            "unsupported OpenAPI version {}",
+            version
+        )))
+    }
+}
+
+/// General information about the API.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#info-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Info {
+    /// The title of the API.
+    pub title: String,
+    /// A short summary of the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// A description of the API. CommonMark syntax MAY be used for rich text representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A URL to the Terms of Service for the API.
+    #[serde(rename = "termsOfService", skip_serializing_if = "Option::is_none")]
+    pub terms_of_service: Option<Url>,
+    /// The contact information for the exposed API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Contact>,
+    /// The license information for the exposed API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<License>,
+    /// The version of the OpenAPI document.
+    pub version: String,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// License information for the exposed API.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#license-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct License {
+    /// The license name used for the API.
+    pub name: String,
+    /// An [SPDX](https://spdx.org/licenses/) license expression for the API. Mutually
+    /// exclusive with `url`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    /// A URL to the license used for the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Url>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// Holds a set of reusable objects for different aspects of the OAS.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#components-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Components {
+    /// Schemas may reference each other with `$ref`, so they are never a separate Reference
+    /// Object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schemas: Option<BTreeMap<String, Schema>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responses: Option<BTreeMap<String, ObjectOrReference<Response>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<BTreeMap<String, ObjectOrReference<Parameter>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<BTreeMap<String, ObjectOrReference<Example>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "requestBodies")]
+    pub request_bodies: Option<BTreeMap<String, ObjectOrReference<RequestBody>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, ObjectOrReference<Header>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "securitySchemes")]
+    pub security_schemes: Option<BTreeMap<String, ObjectOrReference<SecurityScheme>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<BTreeMap<String, ObjectOrReference<Link>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callbacks: Option<BTreeMap<String, ObjectOrReference<Callback>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "pathItems")]
+    pub path_items: Option<BTreeMap<String, ObjectOrReference<PathItem>>>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// Describes the operations available on a single path.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#path-item-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct PathItem {
+    /// Allows for a referenced definition of this path item.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$ref")]
+    pub reference: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub get: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub put: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patch: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Operation>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Vec<ObjectOrReference<Parameter>>>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// Describes a single API operation on a path.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#operation-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Operation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "externalDocs")]
+    pub external_docs: Option<ExternalDoc>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "operationId")]
+    pub operation_id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Vec<ObjectOrReference<Parameter>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "requestBody")]
+    pub request_body: Option<ObjectOrReference<RequestBody>>,
+
+    /// Unlike version 3.0, the responses MAY be omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responses: Option<BTreeMap<String, ObjectOrReference<Response>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callbacks: Option<BTreeMap<String, ObjectOrReference<Callback>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// Describes a single operation parameter.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#parameter-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Parameter {
+    pub name: String,
+
+    /// The location of the parameter: `query`, `header`, `path` or `cookie`.
+    #[serde(rename = "in")]
+    pub location: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(rename = "allowEmptyValue", skip_serializing_if = "Option::is_none")]
+    pub allow_empty_value: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ParameterStyle>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+
+    #[serde(rename = "allowReserved", skip_serializing_if = "Option::is_none")]
+    pub allow_reserved: Option<bool>,
+
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub examples: Option<MediaTypeExample>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<BTreeMap<String, MediaType>>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// A [JSON Schema 2020-12](https://json-schema.org/draft/2020-12/json-schema-core.html) schema,
+/// with the vocabulary added by the OpenAPI Specification.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#schema-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Schema {
+    /// A reference to another schema. Unlike version 3.0, other keywords MAY appear alongside it.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "$ref")]
+    pub ref_path: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The type of the value, or a list of types such as `["string", "null"]`.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
+    pub schema_type: Option<SchemaType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "const")]
+    pub const_value: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "enum")]
+    pub enum_values: Option<Vec<serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    /// Example values; this replaces the single `example` of version 3.0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<serde_json::Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<BTreeMap<String, Schema>>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "additionalProperties"
+    )]
+    pub additional_properties: Option<AdditionalProperties>,
+    #[serde(rename = "maxProperties", skip_serializing_if = "Option::is_none")]
+    pub max_properties: Option<u32>,
+    #[serde(rename = "minProperties", skip_serializing_if = "Option::is_none")]
+    pub min_properties: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<Schema>>,
+    #[serde(rename = "prefixItems", skip_serializing_if = "Option::is_none")]
+    pub prefix_items: Option<Vec<Schema>>,
+    #[serde(rename = "maxItems", skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<u32>,
+    #[serde(rename = "minItems", skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<u32>,
+    #[serde(rename = "uniqueItems", skip_serializing_if = "Option::is_none")]
+    pub unique_items: Option<bool>,
+
+    #[serde(rename = "multipleOf", skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<serde_json::Value>,
+    /// The exclusive upper limit; unlike version 3.0 this is a number rather than a flag on
+    /// `maximum`.
+    #[serde(rename = "exclusiveMaximum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<serde_json::Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<serde_json::Value>,
+    /// The exclusive lower limit; unlike version 3.0 this is a number rather than a flag on
+    /// `minimum`.
+    #[serde(rename = "exclusiveMinimum", skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<serde_json::Number>,
+
+    #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u32>,
+    #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(rename = "contentMediaType", skip_serializing_if = "Option::is_none")]
+    pub content_media_type: Option<String>,
+    #[serde(rename = "contentEncoding", skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+
+    #[serde(rename = "allOf", skip_serializing_if = "Option::is_none")]
+    pub all_of: Option<Vec<Schema>>,
+    #[serde(rename = "oneOf", skip_serializing_if = "Option::is_none")]
+    pub one_of: Option<Vec<Schema>>,
+    #[serde(rename = "anyOf", skip_serializing_if = "Option::is_none")]
+    pub any_of: Option<Vec<Schema>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not: Option<Box<Schema>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Discriminator>,
+
+    #[serde(rename = "readOnly", skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    #[serde(rename = "writeOnly", skip_serializing_if = "Option::is_none")]
+    pub write_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xml: Option<Xml>,
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDoc>,
+    #[serde(rename = "$defs", skip_serializing_if = "Option::is_none")]
+    pub defs: Option<BTreeMap<String, Schema>>,
+
+    /// Any other JSON Schema keywords, as well as specification extensions.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// The `type` of a schema: a single type, or a list of types the value may have.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum SchemaType {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+/// The `additionalProperties` of a schema: a boolean, or a schema the additional properties
+/// must match.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum AdditionalProperties {
+    Bool(bool),
+    Schema(Box<Schema>),
+}
+
+/// Describes a single response from an API Operation.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#response-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Response {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, ObjectOrReference<Header>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<BTreeMap<String, MediaType>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<BTreeMap<String, ObjectOrReference<Link>>>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// The Header Object follows the structure of the Parameter Object, without `name` and `in`.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#header-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Header {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
+    #[serde(rename = "allowEmptyValue", skip_serializing_if = "Option::is_none")]
+    pub allow_empty_value: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ParameterStyle>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+
+    #[serde(rename = "allowReserved", skip_serializing_if = "Option::is_none")]
+    pub allow_reserved: Option<bool>,
+
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub examples: Option<MediaTypeExample>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<BTreeMap<String, MediaType>>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// Describes a single request body.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#request-body-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct RequestBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    pub content: BTreeMap<String, MediaType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// Each Media Type Object provides schema and examples for the media type identified by its key.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#media-type-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct MediaType {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub examples: Option<MediaTypeExample>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<BTreeMap<String, Encoding>>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// A single encoding definition applied to a single schema property.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#encoding-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Encoding {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "contentType")]
+    pub content_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, ObjectOrReference<Header>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none", rename = "allowReserved")]
+    pub allow_reserved: Option<bool>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten, deserialize_with = "extensions::deserialize")]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+/// Defines a security scheme that can be used by the operations.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#security-scheme-object>.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum SecurityScheme {
+    #[serde(rename = "apiKey")]
+    ApiKey {
+        name: String,
+        #[serde(rename = "in")]
+        location: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
+    },
+    #[serde(rename = "http")]
+    Http {
+        scheme: String,
+        #[serde(rename = "bearerFormat", skip_serializing_if = "Option::is_none")]
+        bearer_format: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
+    },
+    /// Authentication with a client certificate during the TLS handshake.
+    #[serde(rename = "mutualTLS")]
+    MutualTls {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
+    },
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        flows: Box<Flows>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
+    },
+    #[serde(rename = "openIdConnect")]
+    OpenIdConnect {
+        #[serde(rename = "openIdConnectUrl")]
+        open_id_connect_url: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        #[serde(flatten, deserialize_with = "extensions::deserialize")]
+        extensions: BTreeMap<String, serde_json::Value>,
+    },
+}
+
+/// A map of possible out-of band callbacks related to the parent operation.
+///
+/// See <https://spec.openapis.org/oas/v3.1.0#callback-object>.
+#[derive(Clone, Debug, Serialize, PartialEq, Default)]
+pub struct Callback {
+    /// Path Item Objects used to define the callback requests and expected responses, keyed by
+    /// a [runtime expression](https://spec.openapis.org/oas/v3.1.0#runtime-expressions).
+    #[serde(flatten)]
+    pub paths: BTreeMap<String, PathItem>,
+
+    /// [Specification extensions](https://spec.openapis.org/oas/v3.1.0#specification-extensions)
+    #[serde(flatten)]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+}
+
+// As in version 3.0, expressions and extensions are split by prefix.
+impl<'de> Deserialize<'de> for Callback {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut callback = Callback::default();
+        for (key, value) in BTreeMap::<String, serde_json::Value>::deserialize(deserializer)? {
+            if key.starts_with("x-") {
+                callback.extensions.insert(key, value);
+            } else {
+                let path = serde_json::from_value(value).map_err(D::Error::custom)?;
+                callback.paths.insert(key, path);
+            }
+        }
+        Ok(callback)
+    }
+}
@@ -0,0 +1,985 @@
+//! Converts OpenAPI 3.0 documents into Postman collections, the reverse of the transpiler.
+//!
+//! Tags become folders, operations become requests against `{{baseUrl}}` with `:param` path
+//! variables, and the examples of each response become saved responses. Where the document has
+//! no example, one is generated from the schema.
+
+use crate::openapi::{self, v3_0 as openapi3};
+use crate::{postman, reason_phrase, Result, URI_TEMPLATE_VARIABLE_RE};
+use std::collections::BTreeMap;
+
+static COLLECTION_SCHEMA: &str =
+    "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+// How deeply generated examples follow nested and referenced schemas.
+static MAX_EXAMPLE_DEPTH: usize = 8;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn from_path(filename: &str) -> Result<String> {
+    let spec = std::fs::read_to_string(filename)?;
+    from_str(&spec)
+}
+
+/// Converts an OpenAPI document, in JSON or YAML, into a Postman collection in JSON. Swagger 2.0
+/// documents are upgraded to OpenAPI 3.0 first.
+pub fn from_str(spec: &str) -> Result<String> {
+    let spec = match openapi::from_reader(spec.as_bytes())? {
+        openapi::OpenApi::V3_1(_) => {
+            anyhow::bail!("OpenAPI 3.1 documents cannot be converted to Postman collections")
+        }
+        spec => spec.to_v3()?.spec,
+    };
+    let collection = Converter::convert(&spec);
+    Ok(serde_json::to_string_pretty(&collection)?)
+}
+
+pub struct Converter<'a> {
+    spec: &'a openapi3::Spec,
+    /// Collection variables, for the base URL, server variables and credentials.
+    variables: Vec<postman::Variable>,
+}
+
+impl<'a> Converter<'a> {
+    pub fn convert(spec: &openapi3::Spec) -> postman::Spec {
+        let mut converter = Converter {
+            spec,
+            variables: vec![],
+        };
+        converter.base_url();
+        let auth = spec
+            .security
+            .as_ref()
+            .and_then(|security| converter.auth(security));
+
+        let mut root = Folder::default();
+        for (path, item) in &spec.paths {
+            for (method, operation) in operations(item) {
+                let request = converter.request_item(path, item, method, operation);
+                folder_chain(operation)
+                    .iter()
+                    .fold(&mut root, |folder, tag| folder.child(tag))
+                    .requests
+                    .push(request);
+            }
+        }
+
+        postman::Spec {
+            auth,
+            event: None,
+            info: postman::Information {
+                postman_id: annotation(&spec.info.extensions, "x-postman-id"),
+                description: spec
+                    .info
+                    .description
+                    .clone()
+                    .map(postman::DescriptionUnion::String),
+                name: spec.info.title.clone(),
+                schema: COLLECTION_SCHEMA.to_string(),
+                version: None,
+            },
+            item: converter.folder_items(root),
+            variable: Some(converter.variables),
+        }
+    }
+
+    /// Returns the subfolders of a folder, in the order of their declared tags, followed by its
+    /// requests.
+    fn folder_items(&self, folder: Folder) -> Vec<postman::Items> {
+        let tags = self.spec.tags.iter().flatten().collect::<Vec<_>>();
+        let mut folders = folder.folders;
+        folders.sort_by_key(|folder| {
+            tags.iter()
+                .position(|tag| tag.name == folder.tag)
+                .unwrap_or(tags.len())
+        });
+
+        let mut items = folders
+            .into_iter()
+            .map(|folder| {
+                let tag = tags.iter().find(|tag| tag.name == folder.tag);
+                let name = tag
+                    .and_then(|tag| annotation(&tag.extensions, "x-postman-name"))
+                    .unwrap_or_else(|| folder.tag.clone());
+                postman::Items {
+                    description: tag
+                        .and_then(|tag| tag.description.clone())
+                        .map(postman::DescriptionUnion::String),
+                    id: tag.and_then(|tag| annotation(&tag.extensions, "x-postman-id")),
+                    item: Some(self.folder_items(folder)),
+                    ..named_item(name)
+                }
+            })
+            .collect::<Vec<_>>();
+        items.extend(folder.requests);
+        items
+    }
+
+    /// Declares the `baseUrl` variable from the first server, and its server variables.
+    fn base_url(&mut self) {
+        let server = self.spec.servers.iter().flatten().next();
+        let url = match server {
+            Some(server) => {
+                for (name, variable) in server.variables.iter().flatten() {
+                    self.add_variable(name, &variable.default, variable.description.clone());
+                }
+                URI_TEMPLATE_VARIABLE_RE
+                    .replace_all(&server.url, "{{$1}}")
+                    .trim_end_matches('/')
+                    .to_string()
+            }
+            None => String::new(),
+        };
+        let description = server.and_then(|server| server.description.clone());
+        self.add_variable("baseUrl", &url, description);
+    }
+
+    fn add_variable(&mut self, key: &str, value: &str, description: Option<String>) {
+        if !self
+            .variables
+            .iter()
+            .any(|variable| variable.key.as_deref() == Some(key))
+        {
+            self.variables.push(variable(
+                key,
+                serde_json::Value::String(value.to_string()),
+                description,
+            ));
+        }
+    }
+
+    fn request_item(
+        &mut self,
+        path: &str,
+        item: &openapi3::PathItem,
+        method: &str,
+        operation: &openapi3::Operation,
+    ) -> postman::Items {
+        let parameters = self.parameters(item, operation);
+        let mut url = self.url(path, &parameters);
+        if let Some(raw) = annotation(&operation.extensions, "x-postman-url") {
+            restore_url(&mut url, &raw);
+        }
+
+        let mut headers = parameters
+            .iter()
+            .filter(|parameter| parameter.location == "header")
+            .map(|parameter| header(&parameter.name, &self.parameter_value(parameter)))
+            .collect::<Vec<_>>();
+        let cookies = parameters
+            .iter()
+            .filter(|parameter| parameter.location == "cookie")
+            .map(|parameter| format!("{}={}", parameter.name, self.parameter_value(parameter)))
+            .collect::<Vec<_>>();
+        if !cookies.is_empty() {
+            headers.push(header("Cookie", &cookies.join("; ")));
+        }
+
+        let body = operation
+            .request_body
+            .as_ref()
+            .and_then(|body| resolve(body, self.components(|c| &c.request_bodies)))
+            .and_then(|body| self.body(body));
+        if let Some((media_type, _)) = &body {
+            headers.push(header("Content-Type", media_type));
+        }
+
+        let request = postman::RequestClass {
+            auth: operation
+                .security
+                .as_ref()
+                .and_then(|security| self.auth(security)),
+            body: body.map(|(_, body)| body),
+            certificate: None,
+            description: operation
+                .description
+                .clone()
+                .map(postman::DescriptionUnion::String),
+            header: Some(postman::HeaderUnion::HeaderArray(headers)),
+            method: Some(method.to_uppercase()),
+            proxy: None,
+            url: Some(postman::Url::UrlClass(url)),
+        };
+        let responses = self.responses(operation, &request);
+
+        let name = annotation(&operation.extensions, "x-postman-name")
+            .or_else(|| operation.summary.clone())
+            .or_else(|| operation.operation_id.clone())
+            .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+        postman::Items {
+            id: annotation(&operation.extensions, "x-postman-id"),
+            request: Some(postman::RequestUnion::RequestClass(request)),
+            response: Some(responses.into_iter().map(Some).collect()),
+            ..named_item(name)
+        }
+    }
+
+    /// Returns the parameters of an operation, including those of its path that it does not
+    /// override.
+    fn parameters(
+        &self,
+        item: &'a openapi3::PathItem,
+        operation: &'a openapi3::Operation,
+    ) -> Vec<&'a openapi3::Parameter> {
+        let resolve_all = |parameters: &'a Option<Vec<_>>| {
+            parameters
+                .iter()
+                .flatten()
+                .filter_map(|parameter| resolve(parameter, self.components(|c| &c.parameters)))
+                .collect::<Vec<&openapi3::Parameter>>()
+        };
+        let mut parameters = resolve_all(&item.parameters);
+        for parameter in resolve_all(&operation.parameters) {
+            parameters.retain(|existing| {
+                existing.name != parameter.name || existing.location != parameter.location
+            });
+            parameters.push(parameter);
+        }
+        parameters
+    }
+
+    fn url(&self, path: &str, parameters: &[&openapi3::Parameter]) -> postman::UrlClass {
+        let path = URI_TEMPLATE_VARIABLE_RE.replace_all(path, ":$1");
+        let segments = path
+            .trim_start_matches('/')
+            .split('/')
+            .map(|segment| postman::PathElement::String(segment.to_string()))
+            .collect();
+
+        let mut query = vec![];
+        for parameter in parameters.iter().filter(|p| p.location == "query") {
+            let description = parameter
+                .description
+                .clone()
+                .map(postman::DescriptionUnion::String);
+            // Exploded arrays repeat the parameter for each item.
+            let values = match self.parameter_example(parameter) {
+                serde_json::Value::Array(values) if parameter.explode != Some(false) => values,
+                value => vec![value],
+            };
+            for value in values {
+                query.push(postman::QueryParam {
+                    description: description.clone(),
+                    disabled: None,
+                    key: Some(parameter.name.clone()),
+                    value: Some(value_string(&value)),
+                });
+            }
+        }
+
+        let variables = parameters
+            .iter()
+            .filter(|parameter| parameter.location == "path")
+            .map(|parameter| {
+                variable(
+                    &parameter.name,
+                    serde_json::Value::String(self.parameter_value(parameter)),
+                    parameter.description.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut raw = format!("{{{{baseUrl}}}}{}", path);
+        if !query.is_empty() {
+            let query = query
+                .iter()
+                .map(|param| {
+                    format!(
+                        "{}={}",
+                        param.key.as_deref().unwrap_or_default(),
+                        param.value.as_deref().unwrap_or_default()
+                    )
+                })
+                .collect::<Vec<_>>();
+            raw = format!("{}?{}", raw, query.join("&"));
+        }
+
+        postman::UrlClass {
+            hash: None,
+            host: Some(postman::Host::StringArray(vec!["{{baseUrl}}".to_string()])),
+            path: Some(postman::UrlPath::UnionArray(segments)),
+            port: None,
+            protocol: None,
+            query: if query.is_empty() { None } else { Some(query) },
+            raw: Some(raw),
+            variable: if variables.is_empty() {
+                None
+            } else {
+                Some(variables)
+            },
+        }
+    }
+
+    fn parameter_example(&self, parameter: &openapi3::Parameter) -> serde_json::Value {
+        parameter
+            .examples
+            .as_ref()
+            .and_then(|examples| self.media_type_examples(examples).into_iter().next())
+            .map(|example| example.value)
+            .or_else(|| {
+                let schema = match (&parameter.schema, &parameter.content) {
+                    (Some(schema), _) => Some(schema),
+                    (None, Some(content)) => preferred_media_type(content)
+                        .and_then(|(_, media)| media.schema.as_ref())
+                        .and_then(|schema| resolve(schema, self.components(|c| &c.schemas))),
+                    (None, None) => None,
+                };
+                schema.map(|schema| self.example(schema, Direction::Request, 0))
+            })
+            .unwrap_or_else(|| serde_json::Value::String(String::new()))
+    }
+
+    fn parameter_value(&self, parameter: &openapi3::Parameter) -> String {
+        match self.parameter_example(parameter) {
+            serde_json::Value::Array(values) => values
+                .iter()
+                .map(value_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            value => value_string(&value),
+        }
+    }
+
+    /// Returns the media type and Postman body of a request body.
+    fn body(&self, body: &openapi3::RequestBody) -> Option<(String, postman::Body)> {
+        let (media_type, media) = preferred_media_type(&body.content)?;
+        let example = self
+            .content_examples(media, Direction::Request)
+            .into_iter()
+            .next()
+            .map(|example| example.value)
+            .unwrap_or(serde_json::Value::Null);
+        let schema = media
+            .schema
+            .as_ref()
+            .and_then(|schema| resolve(schema, self.components(|c| &c.schemas)));
+
+        let mut postman_body = postman::Body {
+            disabled: None,
+            file: None,
+            formdata: None,
+            graphql: None,
+            mode: None,
+            raw: None,
+            urlencoded: None,
+        };
+        let description = |name: &str| {
+            schema
+                .and_then(|schema| schema.properties.as_ref())
+                .and_then(|properties| properties.get(name))
+                .and_then(|property| property.description.clone())
+                .map(postman::DescriptionUnion::String)
+        };
+        let fields = match &example {
+            serde_json::Value::Object(fields) => fields.clone(),
+            _ => serde_json::Map::new(),
+        };
+        match media_type.as_str() {
+            "application/x-www-form-urlencoded" => {
+                postman_body.mode = Some(postman::Mode::Urlencoded);
+                postman_body.urlencoded = Some(
+                    fields
+                        .iter()
+                        .map(|(key, value)| postman::UrlEncodedParameter {
+                            description: description(key),
+                            disabled: None,
+                            key: key.clone(),
+                            value: Some(value_string(value)),
+                        })
+                        .collect(),
+                );
+            }
+            "multipart/form-data" => {
+                postman_body.mode = Some(postman::Mode::Formdata);
+                postman_body.formdata = Some(
+                    fields
+                        .iter()
+                        .map(|(key, value)| {
+                            let file = schema
+                                .and_then(|schema| schema.properties.as_ref())
+                                .and_then(|properties| properties.get(key))
+                                .map_or(false, |property| {
+                                    property.format.as_deref() == Some("binary")
+                                });
+                            let content_type = media
+                                .encoding
+                                .as_ref()
+                                .and_then(|encoding| encoding.get(key))
+                                .and_then(|encoding| encoding.content_type.clone());
+                            postman::FormParameter {
+                                content_type,
+                                description: description(key),
+                                disabled: None,
+                                key: key.clone(),
+                                src: None,
+                                form_parameter_type: Some(
+                                    if file { "file" } else { "text" }.to_string(),
+                                ),
+                                value: if file {
+                                    None
+                                } else {
+                                    Some(value_string(value))
+                                },
+                            }
+                        })
+                        .collect(),
+                );
+            }
+            "application/octet-stream" => {
+                postman_body.mode = Some(postman::Mode::File);
+                postman_body.file = Some(postman::File {
+                    content: None,
+                    src: None,
+                });
+            }
+            _ => {
+                postman_body.mode = Some(postman::Mode::Raw);
+                postman_body.raw = Some(body_text(&example));
+            }
+        }
+        Some((media_type.clone(), postman_body))
+    }
+
+    /// Returns a saved response for each status code, or for each named example of one.
+    fn responses(
+        &self,
+        operation: &openapi3::Operation,
+        request: &postman::RequestClass,
+    ) -> Vec<postman::ResponseClass> {
+        let mut responses = vec![];
+        for (status, response) in &operation.responses {
+            let code = status.parse::<i64>().ok();
+            let description = response
+                .description
+                .clone()
+                .filter(|description| !description.is_empty())
+                .or_else(|| code.map(|code| reason_phrase(code).to_string()))
+                .unwrap_or_else(|| status.clone());
+
+            let mut headers = response
+                .headers
+                .iter()
+                .flatten()
+                .filter_map(|(name, header)| {
+                    let header = resolve(header, self.components(|c| &c.headers))?;
+                    let value = header
+                        .schema
+                        .as_ref()
+                        .map(|schema| value_string(&self.example(schema, Direction::Response, 0)))
+                        .unwrap_or_default();
+                    Some(postman::HeaderElement::Header(self::header(name, &value)))
+                })
+                .collect::<Vec<_>>();
+
+            let content = response.content.as_ref().and_then(preferred_media_type);
+            let examples = match content {
+                Some((media_type, media)) => {
+                    headers.insert(
+                        0,
+                        postman::HeaderElement::Header(header("Content-Type", media_type)),
+                    );
+                    self.content_examples(media, Direction::Response)
+                }
+                None => vec![NamedExample::default()],
+            };
+            let named = examples.len() > 1;
+            for example in examples {
+                let body = content.map(|_| body_text(&example.value));
+                let name = match (example.source_name, example.name) {
+                    (Some(name), _) => name,
+                    (None, Some(name)) if named => format!("{} ({})", description, name),
+                    _ => description.clone(),
+                };
+                responses.push(postman::ResponseClass {
+                    name: Some(name),
+                    body,
+                    code,
+                    cookie: None,
+                    header: Some(postman::Headers::UnionArray(headers.clone())),
+                    id: example.source_id,
+                    original_request: Some(postman::RequestUnion::RequestClass(request.clone())),
+                    response_time: None,
+                    status: code.map(|code| reason_phrase(code).to_string()),
+                });
+            }
+        }
+        responses
+    }
+
+    /// Returns the examples of a media type by name, generating one from its schema for the
+    /// `direction` the body is sent in if there are none.
+    fn content_examples(
+        &self,
+        media: &openapi3::MediaType,
+        direction: Direction,
+    ) -> Vec<NamedExample> {
+        let examples = media
+            .examples
+            .as_ref()
+            .map(|examples| self.media_type_examples(examples))
+            .unwrap_or_default();
+        if !examples.is_empty() {
+            return examples;
+        }
+        let example = media
+            .schema
+            .as_ref()
+            .and_then(|schema| resolve(schema, self.components(|c| &c.schemas)))
+            .map(|schema| self.example(schema, direction, 0))
+            .unwrap_or(serde_json::Value::Null);
+        vec![NamedExample {
+            value: example,
+            ..NamedExample::default()
+        }]
+    }
+
+    fn media_type_examples(&self, examples: &openapi3::MediaTypeExample) -> Vec<NamedExample> {
+        match examples {
+            openapi3::MediaTypeExample::Example { example } => vec![NamedExample {
+                value: example.clone(),
+                ..NamedExample::default()
+            }],
+            openapi3::MediaTypeExample::Examples { examples } => examples
+                .iter()
+                .filter_map(|(name, example)| {
+                    let example = resolve(example, self.components(|c| &c.examples))?;
+                    Some(NamedExample {
+                        name: Some(example.summary.clone().unwrap_or_else(|| name.clone())),
+                        source_name: annotation(&example.extensions, "x-postman-name"),
+                        source_id: annotation(&example.extensions, "x-postman-id"),
+                        value: example.value.clone()?,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Generates an example value from a schema, preferring the values it declares. Properties
+    /// that are not sent in the `direction` of the body, such as a `readOnly` ID in a request,
+    /// are left out.
+    fn example(
+        &self,
+        schema: &openapi3::Schema,
+        direction: Direction,
+        depth: usize,
+    ) -> serde_json::Value {
+        if let Some(example) = schema.example.as_ref().or(schema.default.as_ref()) {
+            return example.clone();
+        }
+        if let Some(value) = schema.enum_values.iter().flatten().next() {
+            return value.clone();
+        }
+        if depth > MAX_EXAMPLE_DEPTH {
+            return serde_json::Value::Null;
+        }
+        let nested = |schema: &openapi3::ObjectOrReference<openapi3::Schema>| {
+            resolve(schema, self.components(|c| &c.schemas))
+                .map(|schema| self.example(schema, direction, depth + 1))
+        };
+        if let Some(ref_path) = &schema.ref_path {
+            let reference = openapi3::ObjectOrReference::Ref {
+                ref_path: ref_path.clone(),
+            };
+            return nested(&reference).unwrap_or(serde_json::Value::Null);
+        }
+        if let Some(all_of) = &schema.all_of {
+            let mut object = serde_json::Map::new();
+            for value in all_of.iter().filter_map(nested) {
+                if let serde_json::Value::Object(fields) = value {
+                    object.extend(fields);
+                }
+            }
+            return serde_json::Value::Object(object);
+        }
+        if let Some(schema) = schema
+            .one_of
+            .iter()
+            .chain(schema.any_of.iter())
+            .flatten()
+            .next()
+        {
+            return nested(schema).unwrap_or(serde_json::Value::Null);
+        }
+
+        match schema.schema_type.as_deref() {
+            Some("array") => serde_json::Value::Array(
+                schema
+                    .items
+                    .iter()
+                    .map(|items| self.example(items, direction, depth + 1))
+                    .collect(),
+            ),
+            Some("integer") | Some("number") => serde_json::Value::from(0),
+            Some("boolean") => serde_json::Value::Bool(true),
+            Some("string") => serde_json::Value::String(
+                match schema.format.as_deref() {
+                    Some("date") => "2021-01-01",
+                    Some("date-time") => "2021-01-01T00:00:00Z",
+                    Some("email") => "user@example.com",
+                    Some("uri") | Some("url") => "https://example.com",
+                    Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                    Some("binary") | Some("byte") => "",
+                    _ => "string",
+                }
+                .to_string(),
+            ),
+            _ if schema.properties.is_some() || schema.schema_type.as_deref() == Some("object") => {
+                serde_json::Value::Object(
+                    schema
+                        .properties
+                        .iter()
+                        .flatten()
+                        .filter(|(_, property)| self.is_sent(property, direction))
+                        .map(|(name, property)| {
+                            (name.clone(), self.example(property, direction, depth + 1))
+                        })
+                        .collect(),
+                )
+            }
+            _ => serde_json::Value::Null,
+        }
+    }
+
+    /// Returns whether a property is sent in the `direction` of the body, following a reference
+    /// to the schema that declares it `readOnly` or `writeOnly`.
+    fn is_sent(&self, property: &openapi3::Schema, direction: Direction) -> bool {
+        let is_sent = |schema: &openapi3::Schema| match direction {
+            Direction::Request => schema.read_only != Some(true),
+            Direction::Response => schema.write_only != Some(true),
+        };
+        match &property.ref_path {
+            Some(ref_path) => {
+                let reference = openapi3::ObjectOrReference::Ref {
+                    ref_path: ref_path.clone(),
+                };
+                resolve(&reference, self.components(|c| &c.schemas)).map_or(true, is_sent)
+                    && is_sent(property)
+            }
+            None => is_sent(property),
+        }
+    }
+
+    /// Converts the first security requirement into Postman auth. An empty requirement, or no
+    /// requirement at all, disables auth.
+    fn auth(&mut self, security: &[openapi3::SecurityRequirement]) -> Option<postman::Auth> {
+        let (name, scopes) = match security.first().and_then(|r| r.iter().next()) {
+            Some(requirement) => requirement,
+            None => return Some(auth(postman::AuthType::Noauth, &[])),
+        };
+        let scheme = self
+            .spec
+            .components
+            .as_ref()
+            .and_then(|components| components.security_schemes.as_ref())
+            .and_then(|schemes| schemes.get(name))
+            .and_then(|scheme| resolve(scheme, self.components(|c| &c.security_schemes)))?;
+
+        match scheme {
+            openapi3::SecurityScheme::Http { scheme, .. } => match scheme.to_lowercase().as_str() {
+                "basic" => Some(self.credentials_auth(postman::AuthType::Basic, "basicAuth")),
+                "digest" => Some(self.credentials_auth(postman::AuthType::Digest, "digestAuth")),
+                "bearer" => {
+                    self.add_variable("bearerToken", "", None);
+                    Some(auth(
+                        postman::AuthType::Bearer,
+                        &[("token", "{{bearerToken}}")],
+                    ))
+                }
+                _ => None,
+            },
+            openapi3::SecurityScheme::ApiKey { name, location, .. } => {
+                if location == "cookie" {
+                    return None;
+                }
+                self.add_variable("apiKey", "", None);
+                Some(auth(
+                    postman::AuthType::Apikey,
+                    &[("key", name), ("value", "{{apiKey}}"), ("in", location)],
+                ))
+            }
+            openapi3::SecurityScheme::OAuth2 { flows, .. } => {
+                let scope = scopes.join(" ");
+                let mut attributes = vec![("scope", scope.as_str()), ("addTokenTo", "header")];
+                let authorization_url;
+                let token_url;
+                if let Some(flow) = &flows.authorization_code {
+                    authorization_url = flow.authorization_url.to_string();
+                    token_url = flow.token_url.to_string();
+                    attributes.push(("grant_type", "authorization_code"));
+                    attributes.push(("authUrl", &authorization_url));
+                    attributes.push(("accessTokenUrl", &token_url));
+                } else if let Some(flow) = &flows.implicit {
+                    authorization_url = flow.authorization_url.to_string();
+                    attributes.push(("grant_type", "implicit"));
+                    attributes.push(("authUrl", &authorization_url));
+                } else if let Some(flow) = &flows.password {
+                    token_url = flow.token_url.to_string();
+                    attributes.push(("grant_type", "password_credentials"));
+                    attributes.push(("accessTokenUrl", &token_url));
+                } else if let Some(flow) = &flows.client_credentials {
+                    token_url = flow.token_url.to_string();
+                    attributes.push(("grant_type", "client_credentials"));
+                    attributes.push(("accessTokenUrl", &token_url));
+                }
+                Some(auth(postman::AuthType::Oauth2, &attributes))
+            }
+            openapi3::SecurityScheme::OpenIdConnect { .. } => None,
+        }
+    }
+
+    /// Returns username and password auth, with the credentials in collection variables.
+    fn credentials_auth(&mut self, auth_type: postman::AuthType, prefix: &str) -> postman::Auth {
+        let username = format!("{}Username", prefix);
+        let password = format!("{}Password", prefix);
+        self.add_variable(&username, "", None);
+        self.add_variable(&password, "", None);
+        auth(
+            auth_type,
+            &[
+                ("username", &format!("{{{{{}}}}}", username)),
+                ("password", &format!("{{{{{}}}}}", password)),
+            ],
+        )
+    }
+
+    fn components<T>(
+        &self,
+        field: impl Fn(&'a openapi3::Components) -> &'a Option<BTreeMap<String, T>>,
+    ) -> Option<&'a BTreeMap<String, T>> {
+        self.spec
+            .components
+            .as_ref()
+            .and_then(|c| field(c).as_ref())
+    }
+}
+
+/// A folder of the collection being built, named by the tag it was declared as.
+#[derive(Default)]
+struct Folder {
+    tag: String,
+    folders: Vec<Folder>,
+    requests: Vec<postman::Items>,
+}
+
+impl Folder {
+    fn child(&mut self, tag: &str) -> &mut Folder {
+        match self.folders.iter().position(|folder| folder.tag == tag) {
+            Some(i) => &mut self.folders[i],
+            None => {
+                self.folders.push(Folder {
+                    tag: tag.to_string(),
+                    ..Folder::default()
+                });
+                self.folders.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+/// Whether a body is sent in a request or received in a response.
+#[derive(Clone, Copy)]
+enum Direction {
+    Request,
+    Response,
+}
+
+/// An example value, with the name of the Postman response it came from when it is annotated.
+#[derive(Default)]
+struct NamedExample {
+    name: Option<String>,
+    source_name: Option<String>,
+    source_id: Option<String>,
+    value: serde_json::Value,
+}
+
+/// Returns the tags of the folders that an operation belongs in: the folder chain recorded by
+/// source annotations, or else its first tag.
+fn folder_chain(operation: &openapi3::Operation) -> Vec<String> {
+    let tags = operation.tags.clone().unwrap_or_default();
+    match operation
+        .extensions
+        .get("x-postman-path")
+        .and_then(|path| path.as_array())
+    {
+        // Tags are unique where folder names may not be, so they identify the folders.
+        Some(path) if path.len() <= tags.len() => tags[..path.len()].to_vec(),
+        Some(path) => path
+            .iter()
+            .filter_map(|name| name.as_str())
+            .map(String::from)
+            .collect(),
+        None => tags.into_iter().take(1).collect(),
+    }
+}
+
+/// Returns a string recorded by source annotations.
+fn annotation(extensions: &BTreeMap<String, serde_json::Value>, key: &str) -> Option<String> {
+    extensions
+        .get(key)
+        .and_then(|value| value.as_str())
+        .map(String::from)
+}
+
+/// Restores the original URL of a request, with the variables it was written with.
+fn restore_url(url: &mut postman::UrlClass, raw: &str) {
+    let base = raw
+        .split(|c| c == '?' || c == '#')
+        .next()
+        .unwrap_or_default();
+    let (protocol, rest) = match base.find("://") {
+        Some(end) => (Some(base[..end].to_string()), &base[end + 3..]),
+        None => (None, base),
+    };
+    let mut segments = rest.split('/');
+    let host = segments.next().unwrap_or_default().to_string();
+    let path = segments.map(String::from).collect::<Vec<_>>();
+
+    if let Some(variables) = &mut url.variable {
+        variables.retain(|variable| {
+            let segment = format!(":{}", variable.key.as_deref().unwrap_or_default());
+            path.contains(&segment)
+        });
+        if variables.is_empty() {
+            url.variable = None;
+        }
+    }
+    url.protocol = protocol;
+    url.host = Some(postman::Host::StringArray(vec![host]));
+    url.path = Some(postman::UrlPath::UnionArray(
+        path.into_iter().map(postman::PathElement::String).collect(),
+    ));
+    url.raw = Some(raw.to_string());
+}
+
+/// Follows references to the components, which may be chained but not circular.
+fn resolve<'b, T>(
+    object: &'b openapi3::ObjectOrReference<T>,
+    components: Option<&'b BTreeMap<String, openapi3::ObjectOrReference<T>>>,
+) -> Option<&'b T> {
+    let mut object = object;
+    for _ in 0..=components.map_or(0, |components| components.len()) {
+        match object {
+            openapi3::ObjectOrReference::Object(object) => return Some(object),
+            openapi3::ObjectOrReference::Ref { ref_path } => {
+                let name = ref_path.rsplit('/').next()?;
+                object = components?.get(name)?;
+            }
+        }
+    }
+    None
+}
+
+fn operations(item: &openapi3::PathItem) -> Vec<(&'static str, &openapi3::Operation)> {
+    vec![
+        ("get", &item.get),
+        ("put", &item.put),
+        ("post", &item.post),
+        ("delete", &item.delete),
+        ("options", &item.options),
+        ("head", &item.head),
+        ("patch", &item.patch),
+        ("trace", &item.trace),
+    ]
+    .into_iter()
+    .filter_map(|(method, operation)| operation.as_ref().map(|operation| (method, operation)))
+    .collect()
+}
+
+/// Returns the JSON media type if there is one, or else the first.
+fn preferred_media_type(
+    content: &BTreeMap<String, openapi3::MediaType>,
+) -> Option<(&String, &openapi3::MediaType)> {
+    content
+        .iter()
+        .find(|(media_type, _)| crate::is_json_media_type(media_type))
+        .or_else(|| content.iter().next())
+}
+
+fn named_item(name: String) -> postman::Items {
+    postman::Items {
+        description: None,
+        event: None,
+        id: None,
+        name: Some(name),
+        protocol_profile_behavior: None,
+        request: None,
+        response: None,
+        variable: None,
+        auth: None,
+        item: None,
+    }
+}
+
+fn header(key: &str, value: &str) -> postman::Header {
+    postman::Header {
+        description: None,
+        disabled: None,
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+fn variable(key: &str, value: serde_json::Value, description: Option<String>) -> postman::Variable {
+    postman::Variable {
+        description: description.map(postman::DescriptionUnion::String),
+        disabled: None,
+        id: None,
+        key: Some(key.to_string()),
+        name: None,
+        system: None,
+        variable_type: Some(postman::VariableType::String),
+        value: Some(value),
+    }
+}
+
+fn auth(auth_type: postman::AuthType, attributes: &[(&str, &str)]) -> postman::Auth {
+    let attributes = Some(postman::AuthAttributeUnion::AuthAttribute21(
+        attributes
+            .iter()
+            .map(|(key, value)| postman::AuthAttribute {
+                key: key.to_string(),
+                auth_type: Some("string".to_string()),
+                value: Some(serde_json::Value::String(value.to_string())),
+            })
+            .collect(),
+    ));
+    let mut auth = postman::Auth {
+        apikey: None,
+        awsv4: None,
+        basic: None,
+        bearer: None,
+        digest: None,
+        hawk: None,
+        noauth: None,
+        ntlm: None,
+        oauth1: None,
+        oauth2: None,
+        auth_type: auth_type.clone(),
+    };
+    match auth_type {
+        postman::AuthType::Apikey => auth.apikey = attributes,
+        postman::AuthType::Basic => auth.basic = attributes,
+        postman::AuthType::Bearer => auth.bearer = attributes,
+        postman::AuthType::Digest => auth.digest = attributes,
+        postman::AuthType::Oauth2 => auth.oauth2 = attributes,
+        _ => {}
+    }
+    auth
+}
+
+/// Returns a value as it is written in a URL or header, without quotes around strings.
+fn value_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(value) => value.clone(),
+        serde_json::Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// Returns the text of a body, formatting structured examples as JSON.
+fn body_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(value) => value.clone(),
+        serde_json::Value::Null => String::new(),
+        value => serde_json::to_string_pretty(value).unwrap_or_default(),
+    }
+}
@@ -1,5 +1,32 @@
 extern crate serde_json;
 
+pub mod v1;
+mod validation;
+
+pub use validation::{validate, ValidationError};
+
+/// Deserializes a collection, upgrading it to the v2.1 format when it is a v1 export.
+pub fn from_str(collection: &str) -> crate::Result<Spec> {
+    let (spec, _) = from_str_with_warnings(collection, false)?;
+    Ok(spec)
+}
+
+/// Validates and deserializes a collection. In lenient mode, the items that fail validation are
+/// skipped and reported in the returned warnings.
+pub fn from_str_with_warnings(
+    collection: &str,
+    lenient: bool,
+) -> crate::Result<(Spec, Vec<String>)> {
+    let mut collection: serde_json::Value = serde_json::from_str(collection)?;
+    let warnings = validate(&mut collection, lenient)?;
+    let spec = if v1::is_v1(&collection) {
+        serde_json::from_value::<v1::Collection>(collection)?.upgrade()
+    } else {
+        serde_json::from_value(collection)?
+    };
+    Ok((spec, warnings))
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
 pub struct Spec {
     #[serde(rename = "auth")]
@@ -24,6 +51,10 @@ pub struct Spec {
 /// Represents authentication helpers provided by Postman
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct Auth {
+    /// The attributes for API key authentication.
+    #[serde(rename = "apikey")]
+    pub apikey: Option<AuthAttributeUnion>,
+
     /// The attributes for [AWS
     /// Auth](http://docs.aws.amazon.com/AmazonS3/latest/dev/RESTAuthentication.html).
     #[serde(rename = "awsv4")]
@@ -407,6 +438,9 @@ pub struct Body {
     #[serde(rename = "formdata")]
     pub formdata: Option<Vec<FormParameter>>,
 
+    #[serde(rename = "graphql")]
+    pub graphql: Option<GraphQlBody>,
+
     /// Postman stores the type of data associated with this request in this field.
     #[serde(rename = "mode")]
     pub mode: Option<Mode>,
@@ -418,6 +452,17 @@ pub struct Body {
     pub urlencoded: Option<Vec<UrlEncodedParameter>>,
 }
 
+/// A GraphQL query and its variables, which Postman sends as a JSON object.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct GraphQlBody {
+    #[serde(rename = "query")]
+    pub query: Option<String>,
+
+    /// The variables of the query, usually as a string holding a JSON object.
+    #[serde(rename = "variables")]
+    pub variables: Option<serde_json::Value>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct File {
     #[serde(rename = "content")]
@@ -443,6 +488,10 @@ pub struct FormParameter {
     #[serde(rename = "key")]
     pub key: String,
 
+    /// The paths of the files uploaded by a `file` form data entity.
+    #[serde(rename = "src")]
+    pub src: Option<Host>,
+
     #[serde(rename = "type")]
     pub form_parameter_type: Option<String>,
 
@@ -577,7 +626,7 @@ pub struct ResponseClass {
     pub id: Option<String>,
 
     #[serde(rename = "originalRequest")]
-    pub original_request: Option<RequestClass>,
+    pub original_request: Option<RequestUnion>,
 
     /// The time taken by the request to complete. If a number, the unit is milliseconds. If the
     /// response is manually created, this can be set to `null`.
@@ -704,6 +753,7 @@ pub enum CollectionVersion {
 /// request URL and the method is assumed to be 'GET'.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum RequestUnion {
     RequestClass(RequestClass),
 
@@ -721,6 +771,7 @@ pub enum HeaderUnion {
 /// A response represents an HTTP response.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum Response {
     //AnythingArray(Vec<Option<serde_json::Value>>),
 
@@ -827,6 +878,9 @@ pub enum Mode {
     #[serde(rename = "formdata")]
     Formdata,
 
+    #[serde(rename = "graphql")]
+    Graphql,
+
     #[serde(rename = "raw")]
     Raw,
 
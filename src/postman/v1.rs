@@ -0,0 +1,671 @@
+//! Support for the Postman Collection Format v1.
+//!
+//! Version 1 collections list their folders and requests side by side and arrange them with
+//! ordered lists of IDs. They are upgraded to the v2.1 model before they are transpiled.
+
+use super::{
+    Auth, Body, DescriptionUnion, Event, FormParameter, GraphQlBody, Header, HeaderElement,
+    HeaderUnion, Headers, Host, Information, Items, Mode, PathElement, QueryParam, RequestClass,
+    RequestUnion, ResponseClass, Script, Spec, Url, UrlClass, UrlEncodedParameter, UrlPath,
+    Variable,
+};
+use std::collections::{BTreeMap, HashSet};
+
+static V2_1_SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Collection {
+    #[serde(rename = "id")]
+    pub id: Option<String>,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "description")]
+    pub description: Option<DescriptionUnion>,
+
+    /// The IDs of the requests at the root of the collection, in order.
+    #[serde(rename = "order")]
+    pub order: Option<Vec<String>>,
+
+    /// The IDs of the folders at the root of the collection, in order.
+    #[serde(rename = "folders_order")]
+    pub folders_order: Option<Vec<String>>,
+
+    /// Every folder of the collection, including nested folders.
+    #[serde(rename = "folders")]
+    pub folders: Option<Vec<Folder>>,
+
+    /// Every request of the collection, including the requests in folders.
+    #[serde(rename = "requests")]
+    pub requests: Option<Vec<Request>>,
+
+    #[serde(rename = "auth")]
+    pub auth: Option<Auth>,
+
+    #[serde(rename = "events")]
+    pub events: Option<Vec<Event>>,
+
+    #[serde(rename = "variables")]
+    pub variables: Option<Vec<Variable>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Folder {
+    #[serde(rename = "id")]
+    pub id: String,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "description")]
+    pub description: Option<DescriptionUnion>,
+
+    /// The IDs of the requests in the folder, in order.
+    #[serde(rename = "order")]
+    pub order: Option<Vec<String>>,
+
+    /// The IDs of the folders nested in the folder, in order.
+    #[serde(rename = "folders_order")]
+    pub folders_order: Option<Vec<String>>,
+
+    #[serde(rename = "auth")]
+    pub auth: Option<Auth>,
+
+    #[serde(rename = "events")]
+    pub events: Option<Vec<Event>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Request {
+    #[serde(rename = "id")]
+    pub id: Option<String>,
+
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "description")]
+    pub description: Option<DescriptionUnion>,
+
+    /// The literal request URL.
+    #[serde(rename = "url")]
+    pub url: Option<String>,
+
+    #[serde(rename = "method")]
+    pub method: Option<String>,
+
+    /// The request headers as `Key: Value` lines. Lines starting with `//` are disabled.
+    #[serde(rename = "headers")]
+    pub headers: Option<String>,
+
+    /// The request headers, broken down. Takes precedence over `headers`.
+    #[serde(rename = "headerData")]
+    pub header_data: Option<Vec<Parameter>>,
+
+    /// The query parameters, broken down. Takes precedence over the query string of `url`.
+    #[serde(rename = "queryParams")]
+    pub query_params: Option<Vec<Parameter>>,
+
+    #[serde(rename = "pathVariables")]
+    pub path_variables: Option<BTreeMap<String, serde_json::Value>>,
+
+    /// The path variables, broken down. Takes precedence over `pathVariables`.
+    #[serde(rename = "pathVariableData")]
+    pub path_variable_data: Option<Vec<Parameter>>,
+
+    /// The type of the request body: `raw`, `urlencoded`, `params`, `binary` or `graphql`.
+    #[serde(rename = "dataMode")]
+    pub data_mode: Option<String>,
+
+    /// The fields of `urlencoded` and `params` bodies.
+    #[serde(rename = "data")]
+    pub data: Option<Data>,
+
+    #[serde(rename = "rawModeData")]
+    pub raw_mode_data: Option<String>,
+
+    #[serde(rename = "graphqlModeData")]
+    pub graphql_mode_data: Option<GraphQlBody>,
+
+    #[serde(rename = "auth")]
+    pub auth: Option<Auth>,
+
+    /// The legacy name of the auth helper, e.g. `basicAuth`, used when `auth` is missing.
+    #[serde(rename = "currentHelper")]
+    pub current_helper: Option<String>,
+
+    #[serde(rename = "helperAttributes")]
+    pub helper_attributes: Option<serde_json::Value>,
+
+    #[serde(rename = "events")]
+    pub events: Option<Vec<Event>>,
+
+    /// The legacy pre-request script, used when `events` is missing.
+    #[serde(rename = "preRequestScript")]
+    pub pre_request_script: Option<String>,
+
+    /// The legacy test script, used when `events` is missing.
+    #[serde(rename = "tests")]
+    pub tests: Option<String>,
+
+    #[serde(rename = "responses")]
+    pub responses: Option<Vec<Response>>,
+}
+
+/// A key-value pair of a header, query parameter, path variable or body field.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Parameter {
+    #[serde(rename = "key")]
+    pub key: String,
+
+    #[serde(rename = "value")]
+    pub value: Option<String>,
+
+    #[serde(rename = "description")]
+    pub description: Option<DescriptionUnion>,
+
+    /// When set to false, the parameter is not sent.
+    #[serde(rename = "enabled")]
+    pub enabled: Option<bool>,
+
+    /// The type of a body field: `text` or `file`.
+    #[serde(rename = "type")]
+    pub parameter_type: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Response {
+    #[serde(rename = "id")]
+    pub id: Option<String>,
+
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "status")]
+    pub status: Option<String>,
+
+    #[serde(rename = "responseCode")]
+    pub response_code: Option<ResponseCode>,
+
+    #[serde(rename = "code")]
+    pub code: Option<i64>,
+
+    #[serde(rename = "headers")]
+    pub headers: Option<Vec<Parameter>>,
+
+    /// The raw text of the response.
+    #[serde(rename = "text")]
+    pub text: Option<String>,
+
+    /// The request that produced the response, or the ID of that request.
+    #[serde(rename = "request")]
+    pub request: Option<ResponseRequest>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct ResponseCode {
+    #[serde(rename = "code")]
+    pub code: i64,
+
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+}
+
+/// The fields of a request body. Exports of raw bodies sometimes hold a string here instead.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum Data {
+    Parameters(Vec<Parameter>),
+
+    String(String),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum ResponseRequest {
+    Request(Box<Request>),
+
+    Id(String),
+}
+
+/// Returns whether a collection is in the v1 format, judging by its declared schema or,
+/// without one, by its shape.
+pub fn is_v1(collection: &serde_json::Value) -> bool {
+    match collection.pointer("/info/schema").and_then(|s| s.as_str()) {
+        Some(schema) => schema.contains("/collection/v1"),
+        None => {
+            collection.get("info").is_none()
+                && collection.get("requests").map_or(false, |r| r.is_array())
+        }
+    }
+}
+
+impl Collection {
+    /// Upgrades the collection to the v2.1 format. Requests that are not listed in any folder or
+    /// order are placed at the root of the collection.
+    pub fn upgrade(self) -> Spec {
+        let folders = self.folders.as_deref().unwrap_or_default();
+        let requests = self.requests.as_deref().unwrap_or_default();
+        let mut upgrade = Upgrade {
+            folders,
+            requests,
+            used: HashSet::new(),
+        };
+
+        let nested = folders
+            .iter()
+            .flat_map(|folder| folder.folders_order.iter().flatten())
+            .collect::<HashSet<_>>();
+        let root_folders = match &self.folders_order {
+            Some(order) => order.clone(),
+            None => folders
+                .iter()
+                .filter(|folder| !nested.contains(&folder.id))
+                .map(|folder| folder.id.clone())
+                .collect(),
+        };
+        let mut item = upgrade.items(&root_folders, self.order.as_deref().unwrap_or_default());
+        for request in requests {
+            if !upgrade.is_used(request) {
+                item.push(request_item(request));
+            }
+        }
+
+        Spec {
+            auth: self.auth,
+            event: self.events,
+            info: Information {
+                postman_id: self.id,
+                description: self.description,
+                name: self.name,
+                schema: V2_1_SCHEMA.to_string(),
+                version: None,
+            },
+            item,
+            variable: self.variables,
+        }
+    }
+}
+
+struct Upgrade<'a> {
+    folders: &'a [Folder],
+    requests: &'a [Request],
+    /// The IDs of the folders and requests that have been placed in the collection.
+    used: HashSet<&'a str>,
+}
+
+impl<'a> Upgrade<'a> {
+    /// Returns the items for the given folders followed by the given requests. Each folder and
+    /// request is placed once, which also guards against folders that contain themselves.
+    fn items(&mut self, folder_ids: &[String], request_ids: &[String]) -> Vec<Items> {
+        let mut items = vec![];
+        for id in folder_ids {
+            let folder = match self.folders.iter().find(|folder| &folder.id == id) {
+                Some(folder) if self.used.insert(&folder.id) => folder,
+                _ => continue,
+            };
+            let item = self.items(
+                folder.folders_order.as_deref().unwrap_or_default(),
+                folder.order.as_deref().unwrap_or_default(),
+            );
+            items.push(Items {
+                auth: folder.auth.clone(),
+                description: folder.description.clone(),
+                event: folder.events.clone(),
+                id: Some(folder.id.clone()),
+                item: Some(item),
+                name: Some(folder.name.clone()),
+                protocol_profile_behavior: None,
+                request: None,
+                response: None,
+                variable: None,
+            });
+        }
+        for id in request_ids {
+            let request = self
+                .requests
+                .iter()
+                .find(|request| request.id.as_ref() == Some(id));
+            if let Some(request) = request {
+                if self.used.insert(id_of(request)) {
+                    items.push(request_item(request));
+                }
+            }
+        }
+        items
+    }
+
+    fn is_used(&self, request: &Request) -> bool {
+        request.id.is_some() && self.used.contains(id_of(request))
+    }
+}
+
+fn id_of(request: &Request) -> &str {
+    request.id.as_deref().unwrap_or_default()
+}
+
+fn request_item(request: &Request) -> Items {
+    let responses = request.responses.as_ref().map(|responses| {
+        responses
+            .iter()
+            .map(|response| Some(response_class(response, request)))
+            .collect()
+    });
+    Items {
+        auth: None,
+        description: None,
+        event: events(request),
+        id: request.id.clone(),
+        item: None,
+        name: request.name.clone(),
+        protocol_profile_behavior: None,
+        request: Some(RequestUnion::RequestClass(request_class(request))),
+        response: responses,
+        variable: None,
+    }
+}
+
+fn request_class(request: &Request) -> RequestClass {
+    RequestClass {
+        auth: request.auth.clone().or_else(|| helper_auth(request)),
+        body: body(request),
+        certificate: None,
+        description: request.description.clone(),
+        header: Some(HeaderUnion::HeaderArray(headers(request))),
+        method: request.method.clone(),
+        proxy: None,
+        url: request
+            .url
+            .as_ref()
+            .map(|raw| Url::UrlClass(url(raw, request))),
+    }
+}
+
+fn response_class(response: &Response, request: &Request) -> ResponseClass {
+    let original_request = match &response.request {
+        Some(ResponseRequest::Request(request)) => request,
+        _ => request,
+    };
+    let headers = response.headers.iter().flatten().map(|header| {
+        HeaderElement::Header(Header {
+            description: header.description.clone(),
+            disabled: header.enabled.map(|enabled| !enabled),
+            key: header.key.clone(),
+            value: header.value.clone().unwrap_or_default(),
+        })
+    });
+    let code = response.response_code.as_ref();
+    ResponseClass {
+        name: response.name.clone(),
+        body: response.text.clone(),
+        code: code.map(|code| code.code).or(response.code),
+        cookie: None,
+        header: Some(Headers::UnionArray(headers.collect())),
+        id: response.id.clone(),
+        original_request: Some(RequestUnion::RequestClass(request_class(original_request))),
+        response_time: None,
+        status: code
+            .and_then(|code| code.name.clone())
+            .or_else(|| response.status.clone()),
+    }
+}
+
+fn headers(request: &Request) -> Vec<Header> {
+    if let Some(headers) = &request.header_data {
+        return headers
+            .iter()
+            .map(|header| Header {
+                description: header.description.clone(),
+                disabled: header.enabled.map(|enabled| !enabled),
+                key: header.key.clone(),
+                value: header.value.clone().unwrap_or_default(),
+            })
+            .collect();
+    }
+    request
+        .headers
+        .iter()
+        .flat_map(|headers| headers.lines())
+        .filter_map(|line| {
+            let (line, disabled) = match line.trim().strip_prefix("//") {
+                Some(line) => (line.trim(), Some(true)),
+                None => (line.trim(), None),
+            };
+            let separator = line.find(':')?;
+            Some(Header {
+                description: None,
+                disabled,
+                key: line[..separator].trim().to_string(),
+                value: line[separator + 1..].trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Breaks the literal request URL down, taking the query parameters and path variables from
+/// their own fields when the request has them.
+fn url(raw: &str, request: &Request) -> UrlClass {
+    let (rest, hash) = split_at(raw, '#');
+    let (rest, query) = split_at(rest, '?');
+    let (protocol, rest) = match rest.find("://") {
+        Some(end) => (Some(rest[..end].to_string()), &rest[end + 3..]),
+        None => (None, rest),
+    };
+    let (host, path) = split_at(rest, '/');
+    let (host, port) = match host.rfind(':') {
+        Some(i) if host[i + 1..].chars().all(|c| c.is_ascii_digit()) => {
+            (&host[..i], Some(host[i + 1..].to_string()))
+        }
+        _ => (host, None),
+    };
+
+    let query = match &request.query_params {
+        Some(params) => params
+            .iter()
+            .map(|param| QueryParam {
+                description: param.description.clone(),
+                disabled: param.enabled.map(|enabled| !enabled),
+                key: Some(param.key.clone()),
+                value: param.value.clone(),
+            })
+            .collect(),
+        None => query
+            .iter()
+            .flat_map(|query| query.split('&'))
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = split_at(pair, '=');
+                QueryParam {
+                    description: None,
+                    disabled: None,
+                    key: Some(key.to_string()),
+                    value: value.map(String::from),
+                }
+            })
+            .collect::<Vec<_>>(),
+    };
+    let variables = match (&request.path_variable_data, &request.path_variables) {
+        (Some(variables), _) => variables
+            .iter()
+            .map(|variable| path_variable(&variable.key, variable.value.clone()))
+            .collect(),
+        (None, Some(variables)) => variables
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    serde_json::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                path_variable(key, Some(value))
+            })
+            .collect(),
+        (None, None) => vec![],
+    };
+
+    UrlClass {
+        hash: hash.map(String::from),
+        host: Some(Host::StringArray(
+            host.split('.').map(String::from).collect(),
+        )),
+        path: path.map(|path| {
+            UrlPath::UnionArray(
+                path.split('/')
+                    .map(|segment| PathElement::String(segment.to_string()))
+                    .collect(),
+            )
+        }),
+        port,
+        protocol,
+        query: if query.is_empty() { None } else { Some(query) },
+        raw: Some(raw.to_string()),
+        variable: if variables.is_empty() {
+            None
+        } else {
+            Some(variables)
+        },
+    }
+}
+
+fn path_variable(key: &str, value: Option<String>) -> Variable {
+    Variable {
+        description: None,
+        disabled: None,
+        id: None,
+        key: Some(key.to_string()),
+        name: None,
+        system: None,
+        variable_type: None,
+        value: value.map(serde_json::Value::String),
+    }
+}
+
+/// Converts the body of a request. Exports give every request a body mode, so bodies without
+/// any data are dropped.
+fn body(request: &Request) -> Option<Body> {
+    let data = match &request.data {
+        Some(Data::Parameters(data)) => &data[..],
+        _ => &[],
+    };
+    let raw = request.raw_mode_data.as_deref().unwrap_or_default();
+    let mut body = Body {
+        disabled: None,
+        file: None,
+        formdata: None,
+        graphql: None,
+        mode: None,
+        raw: None,
+        urlencoded: None,
+    };
+    match request.data_mode.as_deref()? {
+        "raw" if raw.is_empty() => return None,
+        "urlencoded" | "params" if data.is_empty() => return None,
+        "raw" => {
+            body.mode = Some(Mode::Raw);
+            body.raw = request.raw_mode_data.clone();
+        }
+        "urlencoded" => {
+            body.mode = Some(Mode::Urlencoded);
+            body.urlencoded = Some(
+                data.iter()
+                    .map(|param| UrlEncodedParameter {
+                        description: param.description.clone(),
+                        disabled: param.enabled.map(|enabled| !enabled),
+                        key: param.key.clone(),
+                        value: param.value.clone(),
+                    })
+                    .collect(),
+            );
+        }
+        "params" => {
+            body.mode = Some(Mode::Formdata);
+            body.formdata = Some(
+                data.iter()
+                    .map(|param| FormParameter {
+                        content_type: None,
+                        description: param.description.clone(),
+                        disabled: param.enabled.map(|enabled| !enabled),
+                        key: param.key.clone(),
+                        src: None,
+                        form_parameter_type: param.parameter_type.clone(),
+                        value: param.value.clone(),
+                    })
+                    .collect(),
+            );
+        }
+        "binary" => body.mode = Some(Mode::File),
+        "graphql" => {
+            body.mode = Some(Mode::Graphql);
+            body.graphql = Some(request.graphql_mode_data.clone()?);
+        }
+        _ => return None,
+    }
+    Some(body)
+}
+
+/// Converts the legacy auth helper of a request, whose attributes are keyed by name.
+fn helper_auth(request: &Request) -> Option<Auth> {
+    let auth_type = match request.current_helper.as_deref()? {
+        "basicAuth" => "basic",
+        "digestAuth" => "digest",
+        "bearerAuth" => "bearer",
+        "oAuth1" => "oauth1",
+        "oAuth2" => "oauth2",
+        "hawkAuth" => "hawk",
+        "awsSigV4" => "awsv4",
+        "ntlmAuth" => "ntlm",
+        _ => return None,
+    };
+    let attributes = request
+        .helper_attributes
+        .as_ref()
+        .and_then(|attributes| attributes.as_object())
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| key.as_str() != "id" && key.as_str() != "saveToRequest")
+        .map(|(key, value)| serde_json::json!({"key": key, "value": value, "type": "string"}))
+        .collect::<Vec<_>>();
+    serde_json::from_value(serde_json::json!({ "type": auth_type, auth_type: attributes })).ok()
+}
+
+/// Returns the events of a request, falling back to its legacy scripts.
+fn events(request: &Request) -> Option<Vec<Event>> {
+    if request.events.is_some() {
+        return request.events.clone();
+    }
+    let events = [
+        ("prerequest", &request.pre_request_script),
+        ("test", &request.tests),
+    ]
+    .iter()
+    .filter_map(|(listen, script)| {
+        let script = script.as_ref().filter(|script| !script.trim().is_empty())?;
+        Some(Event {
+            disabled: None,
+            id: None,
+            listen: listen.to_string(),
+            script: Some(Script {
+                exec: Some(Host::StringArray(
+                    script.lines().map(String::from).collect(),
+                )),
+                id: None,
+                name: None,
+                src: None,
+                script_type: Some("text/javascript".to_string()),
+            }),
+        })
+    })
+    .collect::<Vec<_>>();
+    if events.is_empty() {
+        None
+    } else {
+        Some(events)
+    }
+}
+
+/// Splits a string at the first occurrence of a character, dropping the character.
+fn split_at(s: &str, c: char) -> (&str, Option<&str>) {
+    match s.find(c) {
+        Some(i) => (&s[..i], Some(&s[i + c.len_utf8()..])),
+        None => (s, None),
+    }
+}
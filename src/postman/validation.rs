@@ -0,0 +1,867 @@
+//! Validation of collections against the shape of the Postman collection schema.
+//!
+//! Problems are reported with the JSON pointers of the values at fault, which the untagged
+//! enums of the model cannot do when deserialization fails.
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+static SUPPORTED_VERSIONS: &[&str] = &["v2.0.0", "v2.1.0"];
+
+static AUTH_TYPES: &[&str] = &[
+    "apikey", "awsv4", "basic", "bearer", "digest", "hawk", "noauth", "ntlm", "oauth1", "oauth2",
+];
+
+static BODY_MODES: &[&str] = &["file", "formdata", "graphql", "raw", "urlencoded"];
+
+static VARIABLE_TYPES: &[&str] = &["any", "boolean", "number", "string"];
+
+/// The problems that keep a collection from being converted.
+#[derive(Error, Debug)]
+#[error("invalid Postman collection:\n{}", .problems.join("\n"))]
+pub struct ValidationError {
+    pub problems: Vec<String>,
+}
+
+/// Validates a v1, v2.0 or v2.1 collection. In lenient mode, the items (or v1 requests) that
+/// fail validation are removed from the collection and reported in the returned warnings;
+/// problems outside of them still fail the whole collection.
+pub fn validate(collection: &mut Value, lenient: bool) -> Result<Vec<String>, ValidationError> {
+    let mut validator = Validator {
+        lenient,
+        ..Validator::default()
+    };
+    if super::v1::is_v1(collection) {
+        validator.v1_collection(collection);
+    } else {
+        validator.collection(collection);
+    }
+    if validator.problems.is_empty() {
+        Ok(validator.skipped)
+    } else {
+        Err(ValidationError {
+            problems: validator.problems,
+        })
+    }
+}
+
+type Check = fn(&mut Validator, &Value, &str);
+
+type SkippableCheck = fn(&mut Validator, &mut Value, &str);
+
+#[derive(Default)]
+struct Validator {
+    lenient: bool,
+    problems: Vec<String>,
+    /// Diagnostics for the items removed in lenient mode.
+    skipped: Vec<String>,
+}
+
+impl Validator {
+    fn collection(&mut self, collection: &mut Value) {
+        let object = match collection.as_object_mut() {
+            Some(object) => object,
+            None => return self.report("#", "an object", collection),
+        };
+        self.required(object, "#", &[("info", Validator::info)]);
+        self.fields(
+            object,
+            "#",
+            &[
+                ("auth", Validator::auth),
+                ("event", Validator::events),
+                ("variable", Validator::variables),
+            ],
+        );
+        match object.get_mut("item") {
+            Some(items) => self.skippable(items, "#/item", "item", Validator::item),
+            None => self.missing("#/item"),
+        }
+    }
+
+    /// Validates the elements of an array each on its own so that broken elements, such as the
+    /// items of a collection or folder, can be skipped in lenient mode.
+    fn skippable(&mut self, values: &mut Value, pointer: &str, noun: &str, check: SkippableCheck) {
+        let values = match values.as_array_mut() {
+            Some(values) => values,
+            None => return self.report(pointer, "an array", values),
+        };
+        let mut keep = vec![];
+        for (i, value) in values.iter_mut().enumerate() {
+            let pointer = format!("{}/{}", pointer, i);
+            let mut validator = Validator {
+                lenient: self.lenient,
+                ..Validator::default()
+            };
+            check(&mut validator, value, &pointer);
+            if validator.problems.is_empty() {
+                self.skipped.extend(validator.skipped);
+                keep.push(true);
+            } else if self.lenient {
+                self.skipped.push(format!(
+                    "{}: skipped an invalid {} ({})",
+                    pointer,
+                    noun,
+                    validator.problems.join("; ")
+                ));
+                keep.push(false);
+            } else {
+                self.problems.extend(validator.problems);
+                keep.push(true);
+            }
+        }
+        let mut keep = keep.into_iter();
+        values.retain(|_| keep.next().unwrap_or(true));
+    }
+
+    fn item(&mut self, item: &mut Value, pointer: &str) {
+        let object = match item.as_object_mut() {
+            Some(object) => object,
+            None => return self.report(pointer, "an object", item),
+        };
+        self.fields(
+            object,
+            pointer,
+            &[
+                ("auth", Validator::auth),
+                ("description", Validator::description),
+                ("event", Validator::events),
+                ("id", Validator::string),
+                ("name", Validator::string),
+                (
+                    "protocolProfileBehavior",
+                    Validator::protocol_profile_behavior,
+                ),
+                ("request", Validator::request_union),
+                ("response", Validator::responses),
+                ("variable", Validator::variables),
+            ],
+        );
+        match object.get_mut("item") {
+            None | Some(Value::Null) => {}
+            Some(items) => {
+                let pointer = format!("{}/item", pointer);
+                self.skippable(items, &pointer, "item", Validator::item)
+            }
+        }
+    }
+
+    /// Validates a v1 collection against the v1 model. Its requests are listed side by side and
+    /// referenced from folders by ID, so they are skipped on their own in lenient mode.
+    fn v1_collection(&mut self, collection: &mut Value) {
+        let object = match collection.as_object_mut() {
+            Some(object) => object,
+            None => return self.report("#", "an object", collection),
+        };
+        self.required(object, "#", &[("name", Validator::string)]);
+        self.fields(
+            object,
+            "#",
+            &[
+                ("auth", Validator::auth),
+                ("description", Validator::description),
+                ("events", Validator::events),
+                ("folders", Validator::v1_folders),
+                ("folders_order", Validator::strings),
+                ("id", Validator::string),
+                ("order", Validator::strings),
+                ("variables", Validator::variables),
+            ],
+        );
+        match object.get_mut("requests") {
+            None | Some(Value::Null) => {}
+            Some(requests) => {
+                let check: SkippableCheck =
+                    |validator, request, pointer| validator.v1_request(request, pointer);
+                self.skippable(requests, "#/requests", "request", check)
+            }
+        }
+    }
+
+    fn v1_folders(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::v1_folder);
+    }
+
+    fn v1_folder(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.required(
+                object,
+                pointer,
+                &[("id", Validator::string), ("name", Validator::string)],
+            );
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("auth", Validator::auth),
+                    ("description", Validator::description),
+                    ("events", Validator::events),
+                    ("folders_order", Validator::strings),
+                    ("order", Validator::strings),
+                ],
+            );
+        }
+    }
+
+    fn v1_request(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("auth", Validator::auth),
+                    ("currentHelper", Validator::string),
+                    ("data", Validator::v1_data),
+                    ("dataMode", Validator::string),
+                    ("description", Validator::description),
+                    ("events", Validator::events),
+                    ("graphqlModeData", Validator::graphql),
+                    ("headerData", Validator::v1_parameters),
+                    ("headers", Validator::string),
+                    ("id", Validator::string),
+                    ("method", Validator::string),
+                    ("name", Validator::string),
+                    ("pathVariableData", Validator::v1_parameters),
+                    ("pathVariables", Validator::any_object),
+                    ("preRequestScript", Validator::string),
+                    ("queryParams", Validator::v1_parameters),
+                    ("rawModeData", Validator::string),
+                    ("responses", Validator::v1_responses),
+                    ("tests", Validator::string),
+                    ("url", Validator::string),
+                ],
+            );
+        }
+    }
+
+    fn v1_data(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Array(_) => self.v1_parameters(value, pointer),
+            _ => self.report(pointer, "a string or an array of parameters", value),
+        }
+    }
+
+    fn v1_parameters(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::v1_parameter);
+    }
+
+    fn v1_parameter(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.required(object, pointer, &[("key", Validator::string)]);
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("description", Validator::description),
+                    ("enabled", Validator::boolean),
+                    ("type", Validator::string),
+                    ("value", Validator::string),
+                ],
+            );
+        }
+    }
+
+    fn v1_responses(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::v1_response);
+    }
+
+    fn v1_response(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("code", Validator::integer),
+                    ("headers", Validator::v1_parameters),
+                    ("id", Validator::string),
+                    ("name", Validator::string),
+                    ("request", Validator::v1_response_request),
+                    ("responseCode", Validator::v1_response_code),
+                    ("status", Validator::string),
+                    ("text", Validator::string),
+                ],
+            );
+        }
+    }
+
+    fn v1_response_code(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.required(object, pointer, &[("code", Validator::integer)]);
+            self.fields(object, pointer, &[("name", Validator::string)]);
+        }
+    }
+
+    fn v1_response_request(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Object(_) => self.v1_request(value, pointer),
+            _ => self.report(pointer, "a string or a request object", value),
+        }
+    }
+
+    fn info(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.required(
+                object,
+                pointer,
+                &[("name", Validator::string), ("schema", Validator::schema)],
+            );
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("_postman_id", Validator::string),
+                    ("description", Validator::description),
+                    ("version", Validator::version),
+                ],
+            );
+        }
+    }
+
+    fn schema(&mut self, value: &Value, pointer: &str) {
+        match value.as_str() {
+            Some(schema)
+                if SUPPORTED_VERSIONS
+                    .iter()
+                    .any(|version| schema.contains(&format!("/{}/", version))) => {}
+            Some(schema) => self.problems.push(format!(
+                "{}: unsupported collection schema {}, expected version {}",
+                pointer,
+                schema,
+                SUPPORTED_VERSIONS.join(" or ")
+            )),
+            None => self.report(pointer, "a string", value),
+        }
+    }
+
+    fn version(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Object(object) => {
+                self.required(
+                    object,
+                    pointer,
+                    &[
+                        ("major", Validator::integer),
+                        ("minor", Validator::integer),
+                        ("patch", Validator::integer),
+                    ],
+                );
+                self.fields(object, pointer, &[("identifier", Validator::string)]);
+            }
+            _ => self.report(pointer, "a string or a version object", value),
+        }
+    }
+
+    fn auth(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.required(object, pointer, &[("type", Validator::auth_type)]);
+        }
+    }
+
+    fn auth_type(&mut self, value: &Value, pointer: &str) {
+        self.one_of(value, pointer, AUTH_TYPES);
+    }
+
+    fn events(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::event);
+    }
+
+    fn event(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.required(object, pointer, &[("listen", Validator::string)]);
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("disabled", Validator::boolean),
+                    ("id", Validator::string),
+                    ("script", Validator::script),
+                ],
+            );
+        }
+    }
+
+    fn script(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("exec", Validator::string_or_strings),
+                    ("id", Validator::string),
+                    ("name", Validator::string),
+                    ("src", Validator::url_union),
+                    ("type", Validator::string),
+                ],
+            );
+        }
+    }
+
+    fn variables(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::variable);
+    }
+
+    fn variable(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("description", Validator::description),
+                    ("disabled", Validator::boolean),
+                    ("id", Validator::string),
+                    ("key", Validator::string),
+                    ("name", Validator::string),
+                    ("system", Validator::boolean),
+                    ("type", Validator::variable_type),
+                ],
+            );
+        }
+    }
+
+    fn variable_type(&mut self, value: &Value, pointer: &str) {
+        self.one_of(value, pointer, VARIABLE_TYPES);
+    }
+
+    fn protocol_profile_behavior(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[("disableBodyPruning", Validator::boolean)],
+            );
+        }
+    }
+
+    fn request_union(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Object(_) => self.request(value, pointer),
+            _ => self.report(pointer, "a string or a request object", value),
+        }
+    }
+
+    fn request(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("auth", Validator::auth),
+                    ("body", Validator::body),
+                    ("certificate", Validator::certificate),
+                    ("description", Validator::description),
+                    ("header", Validator::header_union),
+                    ("method", Validator::string),
+                    ("proxy", Validator::proxy),
+                    ("url", Validator::url_union),
+                ],
+            );
+        }
+    }
+
+    fn url_union(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Object(object) => self.fields(
+                object,
+                pointer,
+                &[
+                    ("hash", Validator::string),
+                    ("host", Validator::string_or_strings),
+                    ("path", Validator::path),
+                    ("port", Validator::string),
+                    ("protocol", Validator::string),
+                    ("query", Validator::query),
+                    ("raw", Validator::string),
+                    ("variable", Validator::variables),
+                ],
+            ),
+            _ => self.report(pointer, "a string or a URL object", value),
+        }
+    }
+
+    fn path(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Array(_) => self.array(value, pointer, Validator::path_segment),
+            _ => self.report(pointer, "a string or an array of path segments", value),
+        }
+    }
+
+    fn path_segment(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Object(object) => self.fields(
+                object,
+                pointer,
+                &[("type", Validator::string), ("value", Validator::string)],
+            ),
+            _ => self.report(pointer, "a string or a path segment object", value),
+        }
+    }
+
+    fn query(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::query_param);
+    }
+
+    fn query_param(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("description", Validator::description),
+                    ("disabled", Validator::boolean),
+                    ("key", Validator::string),
+                    ("value", Validator::string),
+                ],
+            );
+        }
+    }
+
+    fn header_union(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Array(_) => self.array(value, pointer, Validator::header),
+            _ => self.report(pointer, "a string or an array of headers", value),
+        }
+    }
+
+    fn header(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.required(
+                object,
+                pointer,
+                &[("key", Validator::string), ("value", Validator::string)],
+            );
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("description", Validator::description),
+                    ("disabled", Validator::boolean),
+                ],
+            );
+        }
+    }
+
+    fn body(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("disabled", Validator::boolean),
+                    ("file", Validator::file),
+                    ("formdata", Validator::form_parameters),
+                    ("graphql", Validator::graphql),
+                    ("mode", Validator::body_mode),
+                    ("raw", Validator::string),
+                    ("urlencoded", Validator::urlencoded_parameters),
+                ],
+            );
+        }
+    }
+
+    fn body_mode(&mut self, value: &Value, pointer: &str) {
+        self.one_of(value, pointer, BODY_MODES);
+    }
+
+    fn graphql(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(object, pointer, &[("query", Validator::string)]);
+        }
+    }
+
+    fn file(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[("content", Validator::string), ("src", Validator::string)],
+            );
+        }
+    }
+
+    fn form_parameters(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::form_parameter);
+    }
+
+    fn form_parameter(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.required(object, pointer, &[("key", Validator::string)]);
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("contentType", Validator::string),
+                    ("description", Validator::description),
+                    ("disabled", Validator::boolean),
+                    ("src", Validator::string_or_strings),
+                    ("type", Validator::string),
+                    ("value", Validator::string),
+                ],
+            );
+        }
+    }
+
+    fn urlencoded_parameters(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::urlencoded_parameter);
+    }
+
+    fn urlencoded_parameter(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.required(object, pointer, &[("key", Validator::string)]);
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("description", Validator::description),
+                    ("disabled", Validator::boolean),
+                    ("value", Validator::string),
+                ],
+            );
+        }
+    }
+
+    fn certificate(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("cert", Validator::any_object),
+                    ("key", Validator::any_object),
+                    ("matches", Validator::any_array),
+                    ("name", Validator::string),
+                    ("passphrase", Validator::string),
+                ],
+            );
+        }
+    }
+
+    fn proxy(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("disabled", Validator::boolean),
+                    ("host", Validator::string),
+                    ("match", Validator::string),
+                    ("port", Validator::integer),
+                    ("tunnel", Validator::boolean),
+                ],
+            );
+        }
+    }
+
+    fn responses(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::response);
+    }
+
+    fn response(&mut self, value: &Value, pointer: &str) {
+        if value.is_null() {
+            return;
+        }
+        if let Some(object) = self.object(value, pointer) {
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("body", Validator::string),
+                    ("code", Validator::integer),
+                    ("cookie", Validator::cookies),
+                    ("header", Validator::response_headers),
+                    ("id", Validator::string),
+                    ("name", Validator::string),
+                    ("originalRequest", Validator::request_union),
+                    ("responseTime", Validator::number_or_string),
+                    ("status", Validator::string),
+                ],
+            );
+        }
+    }
+
+    fn response_headers(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Array(_) => self.array(value, pointer, Validator::response_header),
+            _ => self.report(pointer, "a string or an array of headers", value),
+        }
+    }
+
+    fn response_header(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            _ => self.header(value, pointer),
+        }
+    }
+
+    fn cookies(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::cookie);
+    }
+
+    fn cookie(&mut self, value: &Value, pointer: &str) {
+        if let Some(object) = self.object(value, pointer) {
+            self.required(
+                object,
+                pointer,
+                &[("domain", Validator::string), ("path", Validator::string)],
+            );
+            self.fields(
+                object,
+                pointer,
+                &[
+                    ("expires", Validator::string),
+                    ("extensions", Validator::any_array),
+                    ("hostOnly", Validator::boolean),
+                    ("httpOnly", Validator::boolean),
+                    ("maxAge", Validator::string),
+                    ("name", Validator::string),
+                    ("secure", Validator::boolean),
+                    ("session", Validator::boolean),
+                    ("value", Validator::string),
+                ],
+            );
+        }
+    }
+
+    fn description(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Object(object) => self.fields(
+                object,
+                pointer,
+                &[("content", Validator::string), ("type", Validator::string)],
+            ),
+            _ => self.report(pointer, "a string or a description object", value),
+        }
+    }
+
+    fn string(&mut self, value: &Value, pointer: &str) {
+        if !value.is_string() {
+            self.report(pointer, "a string", value);
+        }
+    }
+
+    fn string_or_strings(&mut self, value: &Value, pointer: &str) {
+        match value {
+            Value::String(_) => {}
+            Value::Array(_) => self.array(value, pointer, Validator::string),
+            _ => self.report(pointer, "a string or an array of strings", value),
+        }
+    }
+
+    fn strings(&mut self, value: &Value, pointer: &str) {
+        self.array(value, pointer, Validator::string);
+    }
+
+    fn boolean(&mut self, value: &Value, pointer: &str) {
+        if !value.is_boolean() {
+            self.report(pointer, "a boolean", value);
+        }
+    }
+
+    fn integer(&mut self, value: &Value, pointer: &str) {
+        if !value.is_i64() {
+            self.report(pointer, "an integer", value);
+        }
+    }
+
+    fn number_or_string(&mut self, value: &Value, pointer: &str) {
+        if !value.is_number() && !value.is_string() {
+            self.report(pointer, "a number or a string", value);
+        }
+    }
+
+    fn any_object(&mut self, value: &Value, pointer: &str) {
+        self.object(value, pointer);
+    }
+
+    fn any_array(&mut self, value: &Value, pointer: &str) {
+        if !value.is_array() {
+            self.report(pointer, "an array", value);
+        }
+    }
+
+    fn one_of(&mut self, value: &Value, pointer: &str, variants: &[&str]) {
+        match value.as_str() {
+            Some(variant) if variants.contains(&variant) => {}
+            Some(variant) => self.problems.push(format!(
+                "{}: unknown value {}, expected one of {}",
+                pointer,
+                variant,
+                variants.join(", ")
+            )),
+            None => self.report(pointer, "a string", value),
+        }
+    }
+
+    fn array(&mut self, value: &Value, pointer: &str, check: Check) {
+        match value.as_array() {
+            Some(elements) => {
+                for (i, element) in elements.iter().enumerate() {
+                    check(self, element, &format!("{}/{}", pointer, i));
+                }
+            }
+            None => self.report(pointer, "an array", value),
+        }
+    }
+
+    fn object<'v>(&mut self, value: &'v Value, pointer: &str) -> Option<&'v Map<String, Value>> {
+        let object = value.as_object();
+        if object.is_none() {
+            self.report(pointer, "an object", value);
+        }
+        object
+    }
+
+    /// Checks the fields that are present, treating null as missing.
+    fn fields(&mut self, object: &Map<String, Value>, pointer: &str, fields: &[(&str, Check)]) {
+        for (key, check) in fields {
+            match object.get(*key) {
+                None | Some(Value::Null) => {}
+                Some(value) => check(self, value, &format!("{}/{}", pointer, key)),
+            }
+        }
+    }
+
+    fn required(&mut self, object: &Map<String, Value>, pointer: &str, fields: &[(&str, Check)]) {
+        for (key, check) in fields {
+            let pointer = format!("{}/{}", pointer, key);
+            match object.get(*key) {
+                Some(value) => check(self, value, &pointer),
+                None => self.missing(&pointer),
+            }
+        }
+    }
+
+    fn missing(&mut self, pointer: &str) {
+        self.problems
+            .push(format!("{}: missing a required field", pointer));
+    }
+
+    fn report(&mut self, pointer: &str, expected: &str, value: &Value) {
+        self.problems.push(format!(
+            "{}: expected {}, found {}",
+            pointer,
+            expected,
+            kind(value)
+        ));
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
@@ -0,0 +1,267 @@
+//! Static analysis of Postman test scripts.
+//!
+//! Scripts are never executed; the assertions and variable assignments made by common `pm.*`
+//! snippets are recognized with regular expressions.
+
+use crate::postman;
+use regex::Regex;
+
+lazy_static! {
+    static ref STATUS_RE: Regex =
+        Regex::new(r#"\.to\.(?:have|be)\.status\(\s*(?:(\d{3})|["']([^"']+)["'])\s*\)"#).unwrap();
+    static ref STATUS_CODE_RE: Regex = Regex::new(
+        r"(?:pm\.response\.code|responseCode\.code)\)?\s*(?:\.to\.(?:be\.|deep\.)?(?:eql|equal|equals|eq)\(\s*|===?\s*)(\d{3})"
+    )
+    .unwrap();
+    static ref STATUS_ONE_OF_RE: Regex =
+        Regex::new(r"pm\.response\.code\)\s*\.to\.be\.oneOf\(\s*\[([\d\s,]*)\]").unwrap();
+    static ref STATUS_SHORTHAND_RE: Regex = Regex::new(
+        r"pm\.response\.to\.be\.(ok|accepted|badRequest|unauthorized|forbidden|notFound|rateLimited)\b"
+    )
+    .unwrap();
+    static ref CONTENT_TYPE_RE: Regex = Regex::new(
+        r#"(?i)(?:to\.have\.header\(\s*["']content-type["']\s*,|headers\.get\(\s*["']content-type["']\s*\)\s*\)\s*\.to\.(?:include|contain|eql|equal|have\.string)\()\s*["']([^"']+)["']"#
+    )
+    .unwrap();
+    static ref JSON_RE: Regex =
+        Regex::new(r"pm\.response\.(?:json\(\)|to\.(?:be\.json|have\.jsonBody\())").unwrap();
+    static ref JSON_BODY_RE: Regex =
+        Regex::new(r#"\.to\.have\.jsonBody\(\s*["']([^"']+)["']\s*(?:,\s*([^)]*?))?\s*\)"#)
+            .unwrap();
+    static ref JSON_ALIAS_RE: Regex =
+        Regex::new(r"(?:var|let|const)\s+([A-Za-z_$][\w$]*)\s*=\s*pm\.response\.json\(\)")
+            .unwrap();
+    static ref VARIABLE_SET_RE: Regex = Regex::new(
+        r#"(?m)(?:pm\.(?:collectionVariables|environment|globals|variables)\.set|postman\.set(?:Environment|Global)Variable)\(\s*["']([^"']+)["']\s*,\s*(.+?)\)\s*;?\s*$"#
+    )
+    .unwrap();
+    static ref RESPONSE_HEADER_RE: Regex =
+        Regex::new(r#"^pm\.response\.headers\.get\(\s*["']([^"']+)["']\s*\)$"#).unwrap();
+    // Property accesses on the parsed body, or on a variable that may hold it.
+    static ref BODY_ACCESS_RE: Regex = Regex::new(&format!(
+        r"(?:^|[^\w$.])({}|[A-Za-z_$][\w$]*){}",
+        JSON_RECEIVER, ACCESSOR_CHAIN
+    ))
+    .unwrap();
+    static ref BODY_VALUE_RE: Regex = Regex::new(&format!(
+        r"^({}|[A-Za-z_$][\w$]*){}?$",
+        JSON_RECEIVER, ACCESSOR_CHAIN
+    ))
+    .unwrap();
+}
+
+// The parsed response body.
+static JSON_RECEIVER: &str = r"pm\.response\.json\(\)";
+
+// Matches a chain of property accesses such as `.data[0]["id"]`.
+static ACCESSOR_CHAIN: &str = r#"((?:\.[A-Za-z_$][\w$]*|\[\d+\]|\[["'][^"']+["']\])+)"#;
+
+/// A step in a path into a JSON body.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum JsonPathSegment {
+    Property(String),
+    Index,
+}
+
+/// What the test scripts of a request assert about its response.
+#[derive(Debug, Default)]
+pub(crate) struct ResponseAssertions {
+    pub status_codes: Vec<i64>,
+    pub content_types: Vec<String>,
+    /// Paths into the JSON body, with the expected value when the script compares against a
+    /// literal.
+    pub json_paths: Vec<(Vec<JsonPathSegment>, Option<serde_json::Value>)>,
+}
+
+/// Returns the source of a script, joining its lines.
+pub(crate) fn script_source(script: &postman::Script) -> Option<String> {
+    match script.exec.as_ref()? {
+        postman::Host::String(source) => Some(source.clone()),
+        postman::Host::StringArray(lines) => Some(lines.join("\n")),
+    }
+}
+
+/// Returns the source of all enabled test scripts among the events.
+pub(crate) fn test_script(events: &Option<Vec<postman::Event>>) -> String {
+    events
+        .iter()
+        .flatten()
+        .filter(|event| event.listen == "test" && event.disabled != Some(true))
+        .filter_map(|event| event.script.as_ref().and_then(script_source))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub(crate) fn response_assertions(script: &str) -> ResponseAssertions {
+    let mut assertions = ResponseAssertions::default();
+
+    let mut add_status = |code: i64| {
+        if !assertions.status_codes.contains(&code) {
+            assertions.status_codes.push(code);
+        }
+    };
+    for cap in STATUS_RE.captures_iter(script) {
+        let code = match (cap.get(1), cap.get(2)) {
+            (Some(code), _) => code.as_str().parse().ok(),
+            (_, Some(text)) => status_code(text.as_str()),
+            _ => None,
+        };
+        if let Some(code) = code {
+            add_status(code);
+        }
+    }
+    for cap in STATUS_CODE_RE.captures_iter(script) {
+        if let Ok(code) = cap[1].parse() {
+            add_status(code);
+        }
+    }
+    for cap in STATUS_ONE_OF_RE.captures_iter(script) {
+        for code in cap[1].split(',') {
+            if let Ok(code) = code.trim().parse() {
+                add_status(code);
+            }
+        }
+    }
+    for cap in STATUS_SHORTHAND_RE.captures_iter(script) {
+        add_status(match &cap[1] {
+            "ok" => 200,
+            "accepted" => 202,
+            "badRequest" => 400,
+            "unauthorized" => 401,
+            "forbidden" => 403,
+            "notFound" => 404,
+            _ => 429,
+        });
+    }
+
+    for cap in CONTENT_TYPE_RE.captures_iter(script) {
+        if let Some(content_type) = crate::media_type_essence(&cap[1]) {
+            if !assertions.content_types.contains(&content_type) {
+                assertions.content_types.push(content_type);
+            }
+        }
+    }
+    if assertions.content_types.is_empty() && JSON_RE.is_match(script) {
+        assertions
+            .content_types
+            .push("application/json".to_string());
+    }
+
+    for cap in JSON_BODY_RE.captures_iter(script) {
+        let value = cap.get(2).and_then(|v| literal(v.as_str()));
+        add_json_path(&mut assertions.json_paths, parse_path(&cap[1]), value);
+    }
+    let aliases = json_aliases(script);
+    for cap in BODY_ACCESS_RE.captures_iter(script) {
+        if !is_body_receiver(&cap[1], &aliases) {
+            continue;
+        }
+        let chain = cap.get(2).unwrap();
+        let mut path = parse_path(chain.as_str());
+        // A trailing call such as `.forEach(` or `.length` is not part of the body.
+        if script[chain.end()..].starts_with('(')
+            || path.last() == Some(&JsonPathSegment::Property("length".to_string()))
+        {
+            path.pop();
+        }
+        add_json_path(&mut assertions.json_paths, path, None);
+    }
+
+    assertions
+}
+
+/// Returns the variables set from the response by the script, with a runtime expression such as
+/// `$response.body#/id` for the value each one is set to.
+pub(crate) fn variable_assignments(script: &str) -> Vec<(String, String)> {
+    let aliases = json_aliases(script);
+    VARIABLE_SET_RE
+        .captures_iter(script)
+        .filter_map(|cap| {
+            let value = cap[2].trim();
+            let expression = if let Some(header) = RESPONSE_HEADER_RE.captures(value) {
+                format!("$response.header.{}", &header[1])
+            } else {
+                let chain = BODY_VALUE_RE
+                    .captures(value)
+                    .filter(|cap| is_body_receiver(&cap[1], &aliases))?;
+                match chain.get(2) {
+                    Some(chain) => format!("$response.body#{}", json_pointer(chain.as_str())),
+                    None => "$response.body".to_string(),
+                }
+            };
+            Some((cap[1].to_string(), expression))
+        })
+        .collect()
+}
+
+/// Returns the variables the script assigns the parsed response body to.
+fn json_aliases(script: &str) -> Vec<&str> {
+    JSON_ALIAS_RE
+        .captures_iter(script)
+        .map(|cap| cap.get(1).unwrap().as_str())
+        .collect()
+}
+
+fn is_body_receiver(receiver: &str, aliases: &[&str]) -> bool {
+    receiver == "pm.response.json()" || aliases.contains(&receiver)
+}
+
+/// Converts a chain of property accesses into a JSON pointer.
+fn json_pointer(chain: &str) -> String {
+    chain
+        .split(|c| c == '.' || c == '[' || c == ']')
+        .map(|segment| segment.trim_matches(|c| c == '"' || c == '\''))
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+fn add_json_path(
+    paths: &mut Vec<(Vec<JsonPathSegment>, Option<serde_json::Value>)>,
+    path: Vec<JsonPathSegment>,
+    value: Option<serde_json::Value>,
+) {
+    if path.is_empty() {
+        return;
+    }
+    match paths.iter_mut().find(|(p, _)| *p == path) {
+        Some((_, existing)) => {
+            if existing.is_none() {
+                *existing = value;
+            }
+        }
+        None => paths.push((path, value)),
+    }
+}
+
+/// Parses a path such as `data.items[0].id` or `data.items.0.id`.
+fn parse_path(path: &str) -> Vec<JsonPathSegment> {
+    path.split(|c| c == '.' || c == '[' || c == ']')
+        .map(|segment| segment.trim_matches(|c| c == '"' || c == '\''))
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            if segment.chars().all(|c| c.is_ascii_digit()) {
+                JsonPathSegment::Index
+            } else {
+                JsonPathSegment::Property(segment.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Parses a JavaScript literal argument.
+fn literal(arg: &str) -> Option<serde_json::Value> {
+    let arg = arg.trim();
+    if arg.len() >= 2 && arg.starts_with('\'') && arg.ends_with('\'') {
+        return Some(serde_json::Value::String(arg[1..arg.len() - 1].to_string()));
+    }
+    serde_json::from_str(arg).ok()
+}
+
+/// Returns the status code whose registered reason phrase matches the text, e.g. "Created".
+fn status_code(text: &str) -> Option<i64> {
+    (100..600).find(|&code| {
+        crate::registered_reason_phrase(code)
+            .map_or(false, |phrase| phrase.eq_ignore_ascii_case(text.trim()))
+    })
+}
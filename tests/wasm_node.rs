@@ -4221,6 +4221,18 @@ paths:
                   value:
                     message: Hawk Authentication successful
                     status: pass
+      security:
+        - hawkAuth: []
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response body has json saying passed 'status'\", function () {"
+            - "    pm.response.to.have.jsonBody('status', 'pass');"
+            - "});"
+          listen: test
   /basic-auth:
     get:
       tags:
@@ -4272,6 +4284,18 @@ paths:
                 "200":
                   value:
                     authenticated: true
+      security:
+        - basicAuth: []
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response body has json saying 'authenticated'\", function () {"
+            - "    pm.response.to.have.jsonBody('authenticated', true);"
+            - "});"
+          listen: test
   /cookies:
     get:
       tags:
@@ -4327,6 +4351,25 @@ paths:
                   value:
                     cookies:
                       foo2: bar
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test('the \"foo1\" cookie has correct value', function () {"
+            - "    pm.expect(pm.cookies.toObject()).to.have.property('foo1', 'bar1');"
+            - "});"
+            - ""
+            - "pm.test('the \"foo2\" cookie has correct value', function () {"
+            - "    pm.expect(pm.cookies.toObject()).to.have.property('foo2', 'bar2');"
+            - "});"
+            - ""
+            - "pm.test('response body should be valid', function () {"
+            - "    pm.response.to.have.jsonBody('cookies.foo1', 'bar1');"
+            - "    pm.response.to.have.jsonBody('cookies.foo2', 'bar2');"
+            - "});"
+          listen: test
   /cookies/delete:
     get:
       tags:
@@ -4391,6 +4434,32 @@ paths:
                   value:
                     cookies:
                       foo2: bar
+      x-postman-events:
+        - exec:
+            - "pm.test('response should be 200 or 302', function () {"
+            - "    pm.expect(pm.response.code).to.be.oneOf([200, 302]);"
+            - "});"
+            - ""
+            - "pm.test('the \"foo1\" cookie should not be present', function () {"
+            - "    pm.expect(pm.cookies.toObject()).to.not.have.property('foo1');"
+            - "});"
+            - ""
+            - "pm.test('the \"foo2\" cookie should not be present', function () {"
+            - "    pm.expect(pm.cookies.toObject()).to.not.have.property('foo2');"
+            - "});"
+            - ""
+            - "// response code could be either a 200 or a redirection based on the settings of "
+            - // the http client. hence we need to handle both cases
+            - "pm.test('response body should be valid', function () {"
+            - "    if (pm.response.code === 200) {"
+            - "        pm.response.to.not.have.jsonBody('cookies.foo1');"
+            - "        pm.response.to.not.have.jsonBody('cookies.foo2');"
+            - "    }"
+            - "    else {"
+            - "        pm.response.to.have.body('Found. Redirecting to /cookies');"
+            - "    }"
+            - "});"
+          listen: test
   /cookies/set:
     get:
       tags:
@@ -4461,6 +4530,32 @@ paths:
                     cookies:
                       foo1: bar
                       foo2: bar
+      x-postman-events:
+        - exec:
+            - "pm.test('response should be 200 or 302', function () {"
+            - "    pm.expect(pm.response.code).to.be.oneOf([200, 302]);"
+            - "});"
+            - ""
+            - "pm.test('the \"foo1\" cookie has correct value', function () {"
+            - "    pm.expect(pm.cookies.toObject()).to.have.property('foo1', 'bar1');"
+            - "});"
+            - ""
+            - "pm.test('the \"foo2\" cookie has correct value', function () {"
+            - "    pm.expect(pm.cookies.toObject()).to.have.property('foo2', 'bar2');"
+            - "});"
+            - ""
+            - "// response code could be either a 200 or a redirection based on the settings of "
+            - // the http client. hence we need to handle both cases
+            - "pm.test('response body should be valid', function () {"
+            - "    if (pm.response.code === 200) {"
+            - "        pm.response.to.have.jsonBody('cookies.foo1', 'bar1')"
+            - "            .and.have.jsonBody('cookies.foo2', 'bar2');"
+            - "    }"
+            - "    else {"
+            - "        pm.response.to.have.body('Found. Redirecting to /cookies');"
+            - "    }"
+            - "});"
+          listen: test
   /deflate:
     get:
       tags:
@@ -4470,7 +4565,35 @@ paths:
       operationId: deflateCompressedResponse
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  deflated:
+                    type: boolean
+                    example: true
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response has valid json body\", function () {"
+            - "    pm.response.to.have.jsonBody('deflated', true);"
+            - "});"
+            - ""
+            - "pm.test('response headers should indicate valid content encoding', function  () {"
+            - "    pm.response.to.have.header('content-encoding', 'deflate')"
+            - "        .and.have.header('content-type', 'application/json');"
+            - "});"
+            - ""
+            - "pm.test('requesting http client should accept compressed response', function () {"
+            - "    pm.expect(pm.response.json()).to.have.nested.property('headers.accept-encoding')"
+            - "        .and.to.match(/.*deflate.*/);"
+            - "});"
+          listen: test
   /delay/2:
     get:
       tags:
@@ -4518,6 +4641,26 @@ paths:
                 success-response:
                   value:
                     delay: "3"
+      x-postman-events:
+        - exec:
+            - "var _ = require('lodash');"
+            - ""
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response has valid json body\", function () {"
+            - "    pm.response.to.have.jsonBody('delay');"
+            - "});"
+            - ""
+            - // additional sanity tests
+            - "pm.test(\"request must have a valid delay\", function () {"
+            - "    pm.expect(Number(_.get(pm.request, 'url.path[1]'))).to.be.above(0).and.below(10);"
+            - "});"
+            - "pm.test(\"response should take more time than the delay specified\", function () {"
+            - "    pm.expect(pm.response.responseTime).to.be.above(Number(_.get(pm.request, 'url.path[1]')));"
+            - "});"
+          listen: test
   /delete:
     delete:
       tags:
@@ -4531,7 +4674,26 @@ paths:
             example: This is expected to be sent back as part of response body.
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  data:
+                    type: string
+                    example: This is expected to be sent back as part of response body.
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response body has json with form data\", function () {"
+            - "    pm.response.to.have.jsonBody('data', "
+            - "        'This is expected to be sent back as part of response body.');"
+            - "});"
+          listen: test
   /digest-auth:
     get:
       tags:
@@ -4541,7 +4703,25 @@ paths:
       operationId: digestauthRequest
       responses:
         "200":
-          description: ""
+          description: OK
+        "401":
+          description: Unauthorized
+      x-postman-events:
+        - exec:
+            - "tests[\"response code is 401\"] = responseCode.code === 401;"
+            - "tests[\"response has WWW-Authenticate header\"] = (postman.getResponseHeader('WWW-Authenticate'));"
+            - ""
+            - "var authenticateHeader = postman.getResponseHeader('WWW-Authenticate'),"
+            - "    realmStart = authenticateHeader.indexOf('\"',authenticateHeader.indexOf(\"realm\")) + 1 ,"
+            - "    realmEnd = authenticateHeader.indexOf('\"',realmStart),"
+            - "    realm = authenticateHeader.slice(realmStart,realmEnd),"
+            - "    nonceStart = authenticateHeader.indexOf('\"',authenticateHeader.indexOf(\"nonce\")) + 1,"
+            - "    nonceEnd = authenticateHeader.indexOf('\"',nonceStart),"
+            - "    nonce = authenticateHeader.slice(nonceStart,nonceEnd);"
+            - "    "
+            - "postman.setGlobalVariable('echo_digest_realm', realm);"
+            - "postman.setGlobalVariable('echo_digest_nonce', nonce);"
+          listen: test
   /encoding/utf8:
     get:
       tags:
@@ -4551,7 +4731,23 @@ paths:
       operationId: getUtf8EncodedResponse
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            text/html: {}
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response has charset in content-type header\", function () {"
+            - "    pm.response.to.have.header('content-type', 'text/html; charset=utf-8');"
+            - "});"
+            - ""
+            - "pm.test(\"response has chunked transfer encoding header\", function () {"
+            - "    pm.response.to.have.header('transfer-encoding', 'chunked');"
+            - "});"
+          listen: test
   /get:
     get:
       tags:
@@ -4666,6 +4862,17 @@ paths:
                       x-forwarded-port: "443"
                       x-forwarded-proto: https
                     url: "https://postman-echo.com/get?foo1=bar1&foo2=bar2"
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response body has json with request queries\", function () {"
+            - "    pm.response.to.have.jsonBody('args.foo1', 'bar1')"
+            - "        .and.have.jsonBody('args.foo2', 'bar2');"
+            - "});"
+          listen: test
   /gzip:
     get:
       tags:
@@ -4675,7 +4882,35 @@ paths:
       operationId: gzipCompressedResponse
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  gzipped:
+                    type: boolean
+                    example: true
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response has valid json body\", function () {"
+            - "    pm.response.to.have.jsonBody('gzipped', true);"
+            - "});"
+            - ""
+            - "pm.test('response headers should indicate valid content encoding', function  () {"
+            - "    pm.response.to.have.header('content-encoding', 'gzip')"
+            - "        .and.have.header('content-type', 'application/json');"
+            - "});"
+            - ""
+            - "pm.test('requesting http client should accept compressed response', function () {"
+            - "    pm.expect(pm.response.json()).to.have.nested.property('headers.accept-encoding')"
+            - "        .and.to.match(/.*gzip.*/);"
+            - "});"
+          listen: test
   /headers:
     get:
       tags:
@@ -4767,6 +5002,19 @@ paths:
                       user-agent: "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_10_5) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/49.0.2623.110 Safari/537.36"
                       x-forwarded-port: "443"
                       x-forwarded-proto: https
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - // we check to ensire that the headers we sent as request is returned as part of
+            - "// the response body. if you change the request headers, make sure to add them"
+            - // to the tests here
+            - "pm.test(\"sample request header should be returned in response body\", function () {"
+            - "    pm.response.to.have.jsonBody('headers.my-sample-header', 'Lorem ipsum dolor sit amet');"
+            - "});"
+          listen: test
   /ip:
     get:
       tags:
@@ -4776,7 +5024,29 @@ paths:
       operationId: ipAddressInJsonFormat
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  ip: {}
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response has valid json body\", function () {"
+            - "    pm.response.to.have.jsonBody('ip');"
+            - "});"
+            - ""
+            - "pm.test(\"response must return a valid ip address\", function () {"
+            - "    pm.expect(pm.response.json().ip).to"
+            - "        // a really gnarly regular expression to ensure that ip address is in correct format"
+            - "        .match(/^(?:(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)\\.){3}(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)$/);"
+            - "});"
+          listen: test
   /oauth1:
     get:
       tags:
@@ -4891,6 +5161,18 @@ paths:
                     normalized_param_string: oauth_consumer_key=RKCGzna7bv9YD57c_wrong&oauth_nonce=8LTsU2&oauth_signature_method=HMAC-SHA1&oauth_timestamp=1472121295&oauth_version=1.0
                     signing_key: D%2BEdQ-gs%24-%25%402Nu7&
                     status: fail
+      security:
+        - oauth1Auth: []
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response body has json saying passed 'status'\", function () {"
+            - "    pm.response.to.have.jsonBody('status', 'pass');"
+            - "});"
+          listen: test
   /patch:
     patch:
       tags:
@@ -4904,7 +5186,26 @@ paths:
             example: This is expected to be sent back as part of response body.
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  data:
+                    type: string
+                    example: This is expected to be sent back as part of response body.
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response body has json with form data\", function () {"
+            - "    pm.response.to.have.jsonBody('data', "
+            - "        'This is expected to be sent back as part of response body.');"
+            - "});"
+          listen: test
   /post:
     post:
       tags:
@@ -4929,7 +5230,29 @@ paths:
               foo2: bar2
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  form:
+                    type: object
+                    properties:
+                      foo1:
+                        type: string
+                        example: bar1
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response body has json with form data\", function () {"
+            - "    pm.response.to.have.jsonBody('form.foo1', 'bar1')"
+            - "        .and.have.jsonBody('form.foo2', 'bar2');"
+            - "});"
+          listen: test
   /put:
     put:
       tags:
@@ -5014,7 +5337,7 @@ paths:
               - authenticated: true
       responses:
         "200":
-          description: ""
+          description: OK
   /response-headers:
     get:
       tags:
@@ -5070,21 +5393,26 @@ paths:
                 type: string
                 example: response_headers
           content:
-            application/json:
-              schema:
-                type: object
-                properties:
-                  Content-Type:
-                    type: string
-                    example: text/html
-                  test:
-                    type: string
-                    example: response_headers
+            text/html:
               examples:
                 Response headers:
-                  value:
-                    Content-Type: text/html
-                    test: response_headers
+                  value: "{\"Content-Type\":\"text/html\",\"test\":\"response_headers\"}"
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response has the headers sent as part of request query\", function () {"
+            - "    pm.response.to.have.header('foo1', 'bar1')"
+            - "        .and.have.header('foo2', 'bar2');"
+            - "});"
+            - ""
+            - "pm.test(\"sample request param should be returned in response body\", function () {"
+            - "    pm.response.to.have.jsonBody('foo1', 'bar1')"
+            - "        .and.have.jsonBody('foo2', 'bar2');"
+            - "});"
+          listen: test
   /status/200:
     get:
       tags:
@@ -5136,6 +5464,21 @@ paths:
                 "200":
                   value:
                     status: 200
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response has valid json body\", function () {"
+            - "    pm.response.to.have.jsonBody('status');"
+            - "});"
+            - ""
+            - // additional sanity tests
+            - "pm.test(\"status in response body must match the one in request\", function () {"
+            - "    pm.response.to.have.jsonBody('status', Number(_.get(pm.request, 'url.path[1]')));"
+            - "});"
+          listen: test
   /stream/5:
     get:
       tags:
@@ -5145,7 +5488,17 @@ paths:
       operationId: streamedResponse
       responses:
         "200":
-          description: ""
+          description: OK
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response has chunked transfer encoding header\", function () {"
+            - "    pm.response.to.have.header('transfer-encoding', 'chunked');"
+            - "});"
+          listen: test
   /time/add:
     get:
       tags:
@@ -5166,7 +5519,25 @@ paths:
             example: "100"
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  sum:
+                    type: string
+                    example: "Sat Oct 10 2116 00:00:00 GMT+0000"
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should return the added years\", function () {"
+            - "    pm.response.to.have.jsonBody('sum', 'Sat Oct 10 2116 00:00:00 GMT+0000');"
+            - "});"
+          listen: test
   /time/after:
     get:
       tags:
@@ -5187,7 +5558,25 @@ paths:
             example: 2017-10-10
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  after:
+                    type: boolean
+                    example: false
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should say timestamp is not after target\", function () {"
+            - "    pm.response.to.have.jsonBody('after', false);"
+            - "});"
+          listen: test
   /time/before:
     get:
       tags:
@@ -5208,7 +5597,25 @@ paths:
             example: 2017-10-10
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  before:
+                    type: boolean
+                    example: true
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should say timestamp is before target\", function () {"
+            - "    pm.response.to.have.jsonBody('before', true);"
+            - "});"
+          listen: test
   /time/between:
     get:
       tags:
@@ -5234,7 +5641,25 @@ paths:
             example: 2019-10-10
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  between:
+                    type: boolean
+                    example: false
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should say timestamp is not between start and end\", function () {"
+            - "    pm.response.to.have.jsonBody('between', false);"
+            - "});"
+          listen: test
   /time/format:
     get:
       tags:
@@ -5255,7 +5680,25 @@ paths:
             example: mm
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  format:
+                    type: string
+                    example: "20"
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should say whether request query time is valid\", function () {"
+            - "    pm.response.to.have.jsonBody('format', '20');"
+            - "});"
+          listen: test
   /time/leap:
     get:
       tags:
@@ -5271,7 +5714,25 @@ paths:
             example: 2016-10-10
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  leap:
+                    type: boolean
+                    example: true
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should say timestamp is within leap year\", function () {"
+            - "    pm.response.to.have.jsonBody('leap', true);"
+            - "});"
+          listen: test
   /time/now:
     get:
       tags:
@@ -5316,10 +5777,20 @@ paths:
                 type: string
                 example: sails.sid=s%3A2lT3TO7qS1tadeSAp4axl-NcXG9CV6Rf.HGqLY%2FlKEKY4fgCLePaAZs3tCHp%2Bglf7ZOJYlonGeig; Path=/; HttpOnly
           content:
-            text/plain:
+            text/html:
               examples:
                 time as text:
                   value: "Wed, 11 Jan 2017 10:27:12 GMT"
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response time matches server response 'date' header\", function () {"
+            - "    pm.expect(pm.response.text()).to.eql(pm.response.headers.get('date'))"
+            - "});"
+          listen: test
   /time/object:
     get:
       tags:
@@ -5335,7 +5806,28 @@ paths:
             example: 2016-10-10
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json: {}
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should return the date components\", function () {"
+            - "    pm.expect(pm.response.json()).to.eql({"
+            - "        \"years\": 2016,"
+            - "        \"months\": 9,"
+            - "        \"date\": 10,"
+            - "        "
+            - "        \"hours\": 0,"
+            - "        \"minutes\": 0,"
+            - "        \"seconds\": 0,"
+            - "        \"milliseconds\": 0"
+            - "    });"
+            - "});"
+          listen: test
   /time/start:
     get:
       tags:
@@ -5356,7 +5848,25 @@ paths:
             example: month
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  start:
+                    type: string
+                    example: "Sat Oct 01 2016 00:00:00 GMT+0000"
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should return the beginning of month\", function () {"
+            - "    pm.response.to.have.jsonBody('start', 'Sat Oct 01 2016 00:00:00 GMT+0000');"
+            - "});"
+          listen: test
   /time/subtract:
     get:
       tags:
@@ -5377,7 +5887,25 @@ paths:
             example: "50"
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  difference:
+                    type: string
+                    example: "Mon Oct 10 1966 00:00:00 GMT+0000"
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should return the subtracted years\", function () {"
+            - "    pm.response.to.have.jsonBody('difference', 'Mon Oct 10 1966 00:00:00 GMT+0000');"
+            - "});"
+          listen: test
   /time/unit:
     get:
       tags:
@@ -5398,7 +5926,25 @@ paths:
             example: day
       responses:
         "200":
-          description: ""
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  unit:
+                    type: number
+                    example: 1
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should say whether request query unit is valid\", function () {"
+            - "    pm.response.to.have.jsonBody('unit', 1);"
+            - "});"
+          listen: test
   /time/valid:
     get:
       tags:
@@ -5414,7 +5960,7 @@ paths:
             example: 2016-10-10
       responses:
         "200":
-          description: Invalid Timestamp
+          description: Valid Timestamp | Invalid Timestamp
           headers:
             Connection:
               schema:
@@ -5423,15 +5969,15 @@ paths:
             Content-Length:
               schema:
                 type: string
-                example: "15"
+                example: "14"
             Date:
               schema:
                 type: string
-                example: "Wed, 11 Jan 2017 10:27:53 GMT"
+                example: "Wed, 11 Jan 2017 10:27:33 GMT"
             ETag:
               schema:
                 type: string
-                example: "W/\"f-/i9mO/upK91ZtL0BkKFGtw\""
+                example: "W/\"e-OYN7L87J1Ba9oy5mJE2kcA\""
             Server:
               schema:
                 type: string
@@ -5443,7 +5989,7 @@ paths:
             set-cookie:
               schema:
                 type: string
-                example: sails.sid=s%3ATNJaNxi2QCv4RPBb64sIZxQGN1h6IP3g.9sQVAijlsLsh0r7LgffxXa9k2we6UumPEVv%2Bsk4woLI; Path=/; HttpOnly
+                example: sails.sid=s%3AdDGZPe1CZw4mAxGVCHr6RfCADCAwquXa.F5MEm5LJad30JHrSwGGoyWLn2OAAGdvUM7kDtzNfdFI; Path=/; HttpOnly
           content:
             application/json:
               schema:
@@ -5451,11 +5997,24 @@ paths:
                 properties:
                   valid:
                     type: boolean
-                    example: false
+                    example: true
               examples:
                 Invalid Timestamp:
                   value:
                     valid: false
+                Valid Timestamp:
+                  value:
+                    valid: true
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response json should say whether request query time is valid\", function () {"
+            - "    pm.response.to.have.jsonBody('valid', true);"
+            - "});"
+          listen: test
   /transform/collection:
     post:
       tags:
@@ -5738,6 +6297,19 @@ paths:
                         name: A simple POST request with JSON body
                         rawModeData: "{\"text\":\"Duis posuere augue vel cursus pharetra. In luctus a ex nec pretium...\"}"
                         url: "https://postman-echo.com/post"
+      x-postman-events:
+        - exec:
+            - "pm.test(\"response is ok\", function () {"
+            - "    pm.response.to.have.status(200);"
+            - "});"
+            - ""
+            - "pm.test(\"response has almost valid items\", function () {"
+            - "    pm.response.to.have.jsonBody('folders', [])"
+            - "        .and.to.have.jsonBody('order')"
+            - "        .and.to.have.jsonBody('requests[0].name', 'A simple GET request')"
+            - "        .and.to.have.jsonBody('requests[1].name', 'A simple POST request');"
+            - "});"
+          listen: test
   "/{method}/hello":
     get:
       tags:
@@ -5747,7 +6319,7 @@ paths:
       operationId: pathVariables
       responses:
         "200":
-          description: ""
+          description: OK
     parameters:
       - name: method
         in: path
@@ -5756,6 +6328,24 @@ paths:
           type: string
           example: get
         description: An HTTP method.
+components:
+  securitySchemes:
+    basicAuth:
+      type: http
+      scheme: basic
+    digestAuth:
+      type: http
+      scheme: digest
+    hawkAuth:
+      type: apiKey
+      name: Authorization
+      in: header
+      description: Hawk authentication
+    oauth1Auth:
+      type: apiKey
+      name: Authorization
+      in: header
+      description: OAuth 1.0 authentication
 tags:
   - name: Request Methods
     description: "HTTP has multiple request \"verbs\", such as `GET`, `PUT`, `POST`, `DELETE`,\n`PATCH`, `HEAD`, etc. \n\nAn HTTP Method (verb) defines how a request should be interpreted by a server. \nThe endpoints in this section demonstrate various HTTP Verbs. Postman supports \nall the HTTP Verbs, including some rarely used ones, such as `PROPFIND`, `UNLINK`, \netc.\n\nFor details about HTTP Verbs, refer to [RFC 2616](http://www.w3.org/Protocols/rfc2616/rfc2616-sec9.html#sec9)\n"
@@ -5770,5 +6360,12 @@ tags:
   - name: Utilities / Postman Collection
   - name: "Auth: Digest"
     description: "Digest authentication protects an endpoint with a username and password without actually transmitting the password over network.\nOne has to apply a hash function (like MD5, etc) to the username and password before sending them over the network.\n\n> Username: `postman`\n>\n> Password: `password`\n\nUnlike Basic-Auth, authentication happens using two consecutive requests where the first request returns `401 Unauthorised` along with `WWW-Authenticate` header containing information that needs to be used to authenticate subsequent calls.\n\nTo know more about digest authentication, refer to the [Digest Access Authentication](https://en.wikipedia.org/wiki/Digest_access_authentication) wikipedia article.\nThe article on [authentication helpers](https://www.getpostman.com/docs/helpers#digest-auth) elaborates how to use the same within the Postman app."
-  - name: Custom"#;
+  - name: Custom
+x-postman-events:
+  - exec:
+      - ""
+    listen: prerequest
+  - exec:
+      - ""
+    listen: test"#;
 }